@@ -0,0 +1,226 @@
+//! Bech32m Encoding
+//!
+//! General-purpose Bech32m (BIP-350) checksum and bit-conversion helpers,
+//! plus a unified stealth-address encoding built on top of them: the 32-byte
+//! scan (view) and spend public keys are bundled into one human-readable
+//! string the way Zcash encodes unified addresses, instead of three loose
+//! hex blobs with no error detection.
+
+use crate::{CoreError, Result};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [
+    0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3,
+];
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+/// Human-readable prefix for unified stealth addresses.
+pub const STEALTH_ADDRESS_HRP: &str = "sms";
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ (v as u32);
+        for (i, gen) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut v: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    v.push(0);
+    v.extend(hrp.bytes().map(|b| b & 31));
+    v
+}
+
+fn create_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+
+    let mod_value = polymod(&values) ^ BECH32M_CONST;
+    let mut checksum = [0u8; 6];
+    for (i, slot) in checksum.iter_mut().enumerate() {
+        *slot = ((mod_value >> (5 * (5 - i))) & 31) as u8;
+    }
+    checksum
+}
+
+fn verify_checksum(hrp: &str, data: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    polymod(&values) == BECH32M_CONST
+}
+
+/// Encode 5-bit groups (plus their checksum) as a Bech32m string.
+pub fn encode(hrp: &str, data: &[u8]) -> Result<String> {
+    if data.iter().any(|&v| v > 31) {
+        return Err(CoreError::InvalidParameter("data is not 5-bit groups".into()));
+    }
+
+    let checksum = create_checksum(hrp, data);
+    let mut out = String::with_capacity(hrp.len() + 1 + data.len() + checksum.len());
+    out.push_str(hrp);
+    out.push('1');
+
+    let charset: Vec<char> = CHARSET.chars().collect();
+    for &v in data.iter().chain(checksum.iter()) {
+        out.push(charset[v as usize]);
+    }
+    Ok(out)
+}
+
+/// Decode a Bech32m string into its HRP and 5-bit data groups (checksum
+/// stripped), rejecting any single-character error.
+pub fn decode(encoded: &str) -> Result<(String, Vec<u8>)> {
+    let bad = || CoreError::InvalidParameter("invalid bech32m string".into());
+
+    if !encoded.is_ascii() {
+        return Err(bad());
+    }
+    let lower = encoded.to_lowercase();
+    let separator = lower.rfind('1').ok_or_else(bad)?;
+    if separator == 0 || separator + 7 > lower.len() {
+        return Err(bad());
+    }
+
+    let hrp = lower[..separator].to_string();
+    let data_part = &lower[separator + 1..];
+
+    let mut data = Vec::with_capacity(data_part.len());
+    for c in data_part.chars() {
+        let value = CHARSET.find(c).ok_or_else(bad)? as u8;
+        data.push(value);
+    }
+
+    if !verify_checksum(&hrp, &data) {
+        return Err(CoreError::InvalidParameter(
+            "bech32m checksum mismatch".into(),
+        ));
+    }
+
+    data.truncate(data.len() - 6);
+    Ok((hrp, data))
+}
+
+/// Convert a byte slice between bit-widths (e.g. 8-bit bytes to 5-bit
+/// groups), the standard SegWit/Bech32 bit-regrouping routine.
+fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Result<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value = (1u32 << to_bits) - 1;
+    let mut out = Vec::new();
+
+    for &value in data {
+        if (value as u32) >> from_bits != 0 {
+            return Err(CoreError::InvalidParameter("invalid bit width".into()));
+        }
+        acc = (acc << from_bits) | value as u32;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            out.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to_bits - bits)) & max_value) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & max_value) != 0 {
+        return Err(CoreError::InvalidParameter("invalid padding".into()));
+    }
+
+    Ok(out)
+}
+
+/// Bundle a scan (view) public key and a spend public key into one
+/// Bech32m-encoded unified stealth address.
+pub fn encode_stealth_address(
+    scan_public: &RistrettoPoint,
+    spend_public: &RistrettoPoint,
+) -> Result<String> {
+    let mut payload = Vec::with_capacity(64);
+    payload.extend_from_slice(scan_public.compress().as_bytes());
+    payload.extend_from_slice(spend_public.compress().as_bytes());
+
+    let groups = convert_bits(&payload, 8, 5, true)?;
+    encode(STEALTH_ADDRESS_HRP, &groups)
+}
+
+/// Decode a unified stealth address back into its scan and spend public
+/// keys, verifying the checksum first.
+pub fn decode_stealth_address(address: &str) -> Result<(RistrettoPoint, RistrettoPoint)> {
+    let (hrp, groups) = decode(address)?;
+    if hrp != STEALTH_ADDRESS_HRP {
+        return Err(CoreError::InvalidParameter(format!(
+            "unexpected address prefix: {}",
+            hrp
+        )));
+    }
+
+    let payload = convert_bits(&groups, 5, 8, false)?;
+    if payload.len() != 64 {
+        return Err(CoreError::InvalidParameter(
+            "decoded stealth address has the wrong length".into(),
+        ));
+    }
+
+    let scan_bytes: [u8; 32] = payload[..32].try_into().unwrap();
+    let spend_bytes: [u8; 32] = payload[32..].try_into().unwrap();
+
+    let scan_public = CompressedRistretto(scan_bytes)
+        .decompress()
+        .ok_or_else(|| CoreError::InvalidParameter("invalid scan public key".into()))?;
+    let spend_public = CompressedRistretto(spend_bytes)
+        .decompress()
+        .ok_or_else(|| CoreError::InvalidParameter("invalid spend public key".into()))?;
+
+    Ok((scan_public, spend_public))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT as G, scalar::Scalar};
+
+    #[test]
+    fn test_stealth_address_roundtrip() {
+        let scan_public = Scalar::random(&mut rand::thread_rng()) * G;
+        let spend_public = Scalar::random(&mut rand::thread_rng()) * G;
+
+        let encoded = encode_stealth_address(&scan_public, &spend_public).unwrap();
+        assert!(encoded.starts_with("sms1"));
+
+        let (decoded_scan, decoded_spend) = decode_stealth_address(&encoded).unwrap();
+        assert_eq!(decoded_scan, scan_public);
+        assert_eq!(decoded_spend, spend_public);
+    }
+
+    #[test]
+    fn test_single_character_typo_is_rejected() {
+        let scan_public = Scalar::random(&mut rand::thread_rng()) * G;
+        let spend_public = Scalar::random(&mut rand::thread_rng()) * G;
+        let encoded = encode_stealth_address(&scan_public, &spend_public).unwrap();
+
+        let mut chars: Vec<char> = encoded.chars().collect();
+        let last = chars.len() - 1;
+        chars[last] = if chars[last] == 'q' { 'p' } else { 'q' };
+        let corrupted: String = chars.into_iter().collect();
+
+        assert!(decode_stealth_address(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_wrong_hrp_is_rejected() {
+        let data = vec![0u8; 10];
+        let encoded = encode("abc", &data).unwrap();
+        assert!(decode_stealth_address(&encoded).is_err());
+    }
+}
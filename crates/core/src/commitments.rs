@@ -22,16 +22,36 @@
 //! C(v1, r1) + C(v2, r2) = C(v1 + v2, r1 + r2)
 //! ```
 
+use bulletproofs::{BulletproofGens, PedersenGens as BpPedersenGens, RangeProof as BpRangeProof};
 use curve25519_dalek::{
     constants::RISTRETTO_BASEPOINT_POINT,
     ristretto::RistrettoPoint,
     scalar::Scalar,
 };
+use merlin::Transcript;
 use serde::{Deserialize, Serialize};
 use sha2::{Sha512, Digest};
 use rand::Rng;
+use crate::crypto::primitives::{blake2b, ChaCha20Cipher};
 use crate::{CoreError, Result};
 
+/// Domain label for the range proof transcript.
+const RANGE_PROOF_DOMAIN: &[u8] = b"SafeMask-RangeProof";
+
+/// Bit lengths the underlying Bulletproof supports.
+const VALID_BIT_LENGTHS: [usize; 4] = [8, 16, 32, 64];
+
+/// The Bulletproofs generators that pair with [`PedersenCommitment`]'s `g`
+/// and `h`, so commitments created independently of a proof still verify
+/// against it.
+fn pedersen_gens() -> BpPedersenGens {
+    let pedersen = PedersenCommitment::new();
+    BpPedersenGens {
+        B: pedersen.g,
+        B_blinding: pedersen.h,
+    }
+}
+
 /// Helper function to generate random scalar
 pub fn random_scalar() -> Scalar {
     let mut bytes = [0u8; 32];
@@ -210,6 +230,29 @@ pub struct RangeProof {
     
     /// Bit length of the range (e.g., 64 for 64-bit values)
     pub bit_length: usize,
+
+    /// Optional encrypted `(value, blinding)` trailer set by
+    /// [`RangeProof::prove_rewindable`], letting the holder of the rewind
+    /// nonce recover the opening directly from the proof. Absent on proofs
+    /// produced by the ordinary [`RangeProof::prove`].
+    #[serde(default)]
+    pub rewind_trailer: Option<Vec<u8>>,
+}
+
+/// Derive the symmetric key a rewindable proof's trailer is encrypted
+/// under, binding both the shared rewind nonce and an application-specific
+/// separator tag so nonces cannot be reused across contexts.
+///
+/// `k = blake2b(rewind_nonce || key_separator)[..32]`
+fn rewind_key(rewind_nonce: &[u8], key_separator: &[u8]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(rewind_nonce.len() + key_separator.len());
+    preimage.extend_from_slice(rewind_nonce);
+    preimage.extend_from_slice(key_separator);
+
+    let hash = blake2b(&preimage);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hash[..32]);
+    key
 }
 
 impl RangeProof {
@@ -231,25 +274,132 @@ impl RangeProof {
         blinding: &Scalar,
         bit_length: usize,
     ) -> Result<Self> {
-        // Verify value is in range
+        if !VALID_BIT_LENGTHS.contains(&bit_length) {
+            return Err(CoreError::InvalidParameter(format!(
+                "bit_length must be one of {:?}, got {}",
+                VALID_BIT_LENGTHS, bit_length
+            )));
+        }
         if bit_length < 64 && value >= (1u64 << bit_length) {
             return Err(CoreError::InvalidParameter(
                 format!("Value {} exceeds {}-bit range", value, bit_length)
             ));
         }
-        
-        // TODO: Integrate with bulletproofs crate for actual proof generation
-        // For now, return a placeholder
-        // In production, use: bulletproofs::RangeProof::prove_single(...)
-        
-        let proof_bytes = vec![0u8; 672]; // Typical bulletproof size
-        
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(bit_length, 1);
+        let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+
+        let (proof, _commitment) = BpRangeProof::prove_single(
+            &bp_gens,
+            &pc_gens,
+            &mut transcript,
+            value,
+            blinding,
+            bit_length,
+        )
+        .map_err(|e| CoreError::Commitment(format!("range proof generation failed: {:?}", e)))?;
+
         Ok(RangeProof {
-            proof_bytes,
+            proof_bytes: proof.to_bytes(),
             bit_length,
+            rewind_trailer: None,
         })
     }
-    
+
+    /// As [`Self::prove`], but additionally encrypts the `(value, blinding)`
+    /// opening into a trailer keyed off `rewind_nonce` and `key_separator`.
+    /// Anyone holding the same `(rewind_nonce, key_separator)` pair can later
+    /// recover the opening from the proof via [`Self::rewind`] — useful for
+    /// stateless wallet recovery or auditor-assisted scanning.
+    ///
+    /// This is an encrypted side channel carried in [`Self::rewind_trailer`]
+    /// alongside the unchanged proof bytes, not
+    /// the `s_L`/`s_R`/`A`/`S` blinding-vector derivation the feature this
+    /// was modeled on (dalek's rewindable Bulletproofs) uses: the
+    /// `bulletproofs` crate's public API (`prove_single`/`verify_single`)
+    /// takes the blinding factor but doesn't expose a hook to substitute
+    /// deterministic randomness for those internal vectors, so genuine
+    /// "recoverable from the `RangeProof` bytes alone" rewinding isn't
+    /// buildable against it. The trailer gets the same recovery guarantee
+    /// (same nonce ⇒ same opening) at the cost of a few extra bytes on the
+    /// wire rather than being folded into the proof's own commitments.
+    pub fn prove_rewindable(
+        value: u64,
+        blinding: &Scalar,
+        bit_length: usize,
+        rewind_nonce: &[u8],
+        key_separator: &[u8],
+    ) -> Result<Self> {
+        let mut proof = Self::prove(value, blinding, bit_length)?;
+
+        let key = rewind_key(rewind_nonce, key_separator);
+        let mut opening = Vec::with_capacity(40);
+        opening.extend_from_slice(&value.to_le_bytes());
+        opening.extend_from_slice(blinding.as_bytes());
+
+        proof.rewind_trailer = Some(ChaCha20Cipher::new(&key).encrypt(&opening)?);
+        Ok(proof)
+    }
+
+    /// Recover the `(value, blinding)` opening from a proof produced by
+    /// [`Self::prove_rewindable`], given the same rewind nonce and key
+    /// separator used to create it.
+    ///
+    /// Returns [`CoreError::Commitment`] if the proof carries no rewind
+    /// trailer, or if the nonce/separator don't match what it was created
+    /// with (the trailer fails to authenticate).
+    pub fn rewind(&self, rewind_nonce: &[u8], key_separator: &[u8]) -> Result<(u64, Scalar)> {
+        let trailer = self.rewind_trailer.as_ref().ok_or_else(|| {
+            CoreError::Commitment("proof has no rewind trailer".into())
+        })?;
+
+        let key = rewind_key(rewind_nonce, key_separator);
+        let opening = ChaCha20Cipher::new(&key).decrypt(trailer).map_err(|_| {
+            CoreError::Commitment(
+                "rewind failed: wrong rewind nonce or key separator".into(),
+            )
+        })?;
+
+        if opening.len() != 40 {
+            return Err(CoreError::Commitment("corrupt rewind trailer".into()));
+        }
+
+        let mut value_bytes = [0u8; 8];
+        value_bytes.copy_from_slice(&opening[..8]);
+        let value = u64::from_le_bytes(value_bytes);
+
+        let mut blinding_bytes = [0u8; 32];
+        blinding_bytes.copy_from_slice(&opening[8..40]);
+        let blinding = Scalar::from_canonical_bytes(blinding_bytes)
+            .into_option()
+            .ok_or_else(|| CoreError::Commitment("rewound blinding factor is invalid".into()))?;
+
+        let pedersen = PedersenCommitment::new();
+        let expected = pedersen.commit(value, &blinding);
+        let proof = BpRangeProof::from_bytes(&self.proof_bytes)
+            .map_err(|_| CoreError::Commitment("malformed range proof bytes".into()))?;
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(self.bit_length, 1);
+        let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+        if proof
+            .verify_single(
+                &bp_gens,
+                &pc_gens,
+                &mut transcript,
+                &expected.point.compress(),
+                self.bit_length,
+            )
+            .is_err()
+        {
+            return Err(CoreError::Commitment(
+                "rewound opening does not match the committed proof".into(),
+            ));
+        }
+
+        Ok((value, blinding))
+    }
+
     /// Verify a range proof
     ///
     /// # Arguments
@@ -260,26 +410,251 @@ impl RangeProof {
     ///
     /// true if the proof is valid (value is in range)
     pub fn verify(&self, commitment: &Commitment) -> bool {
-        // TODO: Integrate with bulletproofs crate for actual verification
-        // For now, return true for placeholder
-        // In production, use: bulletproofs::RangeProof::verify_single(...)
-        
-        true
+        if !VALID_BIT_LENGTHS.contains(&self.bit_length) {
+            return false;
+        }
+
+        let proof = match BpRangeProof::from_bytes(&self.proof_bytes) {
+            Ok(proof) => proof,
+            Err(_) => return false,
+        };
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(self.bit_length, 1);
+        let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+
+        proof
+            .verify_single(
+                &bp_gens,
+                &pc_gens,
+                &mut transcript,
+                &commitment.point.compress(),
+                self.bit_length,
+            )
+            .is_ok()
     }
-    
-    /// Batch verify multiple range proofs (more efficient)
+
+    /// Prove that every value in `values` is in `[0, 2^bit_length)` with a
+    /// single proof whose size grows only with `log2(bit_length * values.len())`,
+    /// rather than one full proof per value. This is the aggregation mode
+    /// from the Solana zk-token-sdk and dalek bulletproofs: `bit_length *
+    /// values.len()` must be a power of two.
+    pub fn prove_aggregated(
+        values: &[u64],
+        blindings: &[Scalar],
+        bit_length: usize,
+    ) -> Result<Self> {
+        if !VALID_BIT_LENGTHS.contains(&bit_length) {
+            return Err(CoreError::InvalidParameter(format!(
+                "bit_length must be one of {:?}, got {}",
+                VALID_BIT_LENGTHS, bit_length
+            )));
+        }
+        if values.len() != blindings.len() {
+            return Err(CoreError::InvalidParameter(
+                "values and blindings must have the same length".into(),
+            ));
+        }
+        if values.is_empty() {
+            return Err(CoreError::InvalidParameter(
+                "prove_aggregated requires at least one value".into(),
+            ));
+        }
+        if !(bit_length * values.len()).is_power_of_two() {
+            return Err(CoreError::InvalidParameter(format!(
+                "bit_length * values.len() ({} * {}) must be a power of two",
+                bit_length,
+                values.len()
+            )));
+        }
+        for &value in values {
+            if bit_length < 64 && value >= (1u64 << bit_length) {
+                return Err(CoreError::InvalidParameter(format!(
+                    "value {} exceeds {}-bit range",
+                    value, bit_length
+                )));
+            }
+        }
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(bit_length, values.len());
+        let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+
+        let (proof, _commitments) = BpRangeProof::prove_multiple(
+            &bp_gens,
+            &pc_gens,
+            &mut transcript,
+            values,
+            blindings,
+            bit_length,
+        )
+        .map_err(|e| {
+            CoreError::Commitment(format!("aggregated range proof generation failed: {:?}", e))
+        })?;
+
+        Ok(RangeProof {
+            proof_bytes: proof.to_bytes(),
+            bit_length,
+            rewind_trailer: None,
+        })
+    }
+
+    /// Verify an aggregated proof produced by [`Self::prove_aggregated`]
+    /// against the matching set of commitments, in their original order,
+    /// with a single combined multiscalar verification.
+    pub fn verify_aggregated(&self, commitments: &[Commitment]) -> bool {
+        if commitments.is_empty() || !VALID_BIT_LENGTHS.contains(&self.bit_length) {
+            return false;
+        }
+        if !(self.bit_length * commitments.len()).is_power_of_two() {
+            return false;
+        }
+
+        let proof = match BpRangeProof::from_bytes(&self.proof_bytes) {
+            Ok(proof) => proof,
+            Err(_) => return false,
+        };
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(self.bit_length, commitments.len());
+        let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+
+        let compressed: Vec<_> = commitments.iter().map(|c| c.point.compress()).collect();
+
+        proof
+            .verify_multiple(&bp_gens, &pc_gens, &mut transcript, &compressed, self.bit_length)
+            .is_ok()
+    }
+
+    /// Verify several independently-generated range proofs against their
+    /// matching commitments.
+    ///
+    /// Each proof is checked with its own `verify_single` multiscalar
+    /// multiplication; the `bulletproofs` crate doesn't expose the
+    /// per-proof multiscalar terms its public API would need to combine
+    /// independent proofs into a single check the way [`Self::prove_aggregated`]
+    /// combines independent *values* into one proof up front. Callers who
+    /// want that one-multiscalar-check efficiency across many values should
+    /// use [`Self::prove_aggregated`]/[`Self::verify_aggregated`] instead of
+    /// proving each value separately and batching after the fact.
     pub fn verify_batch(proofs: &[RangeProof], commitments: &[Commitment]) -> bool {
         if proofs.len() != commitments.len() {
             return false;
         }
-        
-        // TODO: Implement batch verification
-        // In production, use bulletproofs batch verification
-        
-        proofs.iter()
+
+        proofs
+            .iter()
             .zip(commitments.iter())
             .all(|(proof, commitment)| proof.verify(commitment))
     }
+
+    /// Prove `value < upper_bound` for an arbitrary (non-power-of-two)
+    /// `upper_bound`, binding `domain` into the proof's transcript so a
+    /// proof generated for one application context cannot be replayed in
+    /// another. Follows fastcrypto's `prove_bit_length` API: internally
+    /// `upper_bound` is rounded up to the smallest supported Bulletproof
+    /// bit length.
+    ///
+    /// The `value < upper_bound` check is made here, before proving; the
+    /// proof itself attests only `value < 2^bit_length` for that rounded-up
+    /// bit length, so [`Self::verify_bit_length`] also accepts a proof for a
+    /// value in `[upper_bound, 2^bit_length)`.
+    pub fn prove_bit_length(
+        value: u64,
+        blinding: &Scalar,
+        upper_bound: u64,
+        domain: &[u8],
+    ) -> Result<Self> {
+        let bit_length = bit_length_for_upper_bound(upper_bound)?;
+        if value >= upper_bound {
+            return Err(CoreError::InvalidParameter(format!(
+                "value {} does not satisfy upper bound {}",
+                value, upper_bound
+            )));
+        }
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(bit_length, 1);
+        let mut transcript = domain_transcript(domain);
+
+        let (proof, _commitment) = BpRangeProof::prove_single(
+            &bp_gens,
+            &pc_gens,
+            &mut transcript,
+            value,
+            blinding,
+            bit_length,
+        )
+        .map_err(|e| CoreError::Commitment(format!("bit-length proof generation failed: {:?}", e)))?;
+
+        Ok(RangeProof {
+            proof_bytes: proof.to_bytes(),
+            bit_length,
+            rewind_trailer: None,
+        })
+    }
+
+    /// Verify a proof produced by [`Self::prove_bit_length`] against the
+    /// same `upper_bound` and `domain` it was created with. It checks that
+    /// the committed value is below `2^bit_length` for the bit length that
+    /// `upper_bound` rounds up to, not below `upper_bound` itself.
+    pub fn verify_bit_length(&self, commitment: &Commitment, upper_bound: u64, domain: &[u8]) -> bool {
+        let expected_bit_length = match bit_length_for_upper_bound(upper_bound) {
+            Ok(bits) => bits,
+            Err(_) => return false,
+        };
+        if self.bit_length != expected_bit_length {
+            return false;
+        }
+
+        let proof = match BpRangeProof::from_bytes(&self.proof_bytes) {
+            Ok(proof) => proof,
+            Err(_) => return false,
+        };
+
+        let pc_gens = pedersen_gens();
+        let bp_gens = BulletproofGens::new(self.bit_length, 1);
+        let mut transcript = domain_transcript(domain);
+
+        proof
+            .verify_single(
+                &bp_gens,
+                &pc_gens,
+                &mut transcript,
+                &commitment.point.compress(),
+                self.bit_length,
+            )
+            .is_ok()
+    }
+}
+
+/// Smallest supported Bulletproof bit length whose range `[0, 2^bits)`
+/// covers `[0, upper_bound)`.
+fn bit_length_for_upper_bound(upper_bound: u64) -> Result<usize> {
+    if upper_bound == 0 {
+        return Err(CoreError::InvalidParameter(
+            "upper_bound must be greater than zero".into(),
+        ));
+    }
+
+    VALID_BIT_LENGTHS
+        .iter()
+        .copied()
+        .find(|&bits| bits == 64 || upper_bound <= (1u64 << bits))
+        .ok_or_else(|| {
+            CoreError::InvalidParameter(format!(
+                "upper_bound {} exceeds the supported 64-bit range",
+                upper_bound
+            ))
+        })
+}
+
+/// Build a range-proof transcript domain-separated by `domain`, so proofs
+/// bound to one application context fail verification under another.
+fn domain_transcript(domain: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(RANGE_PROOF_DOMAIN);
+    transcript.append_message(b"domain", domain);
+    transcript
 }
 
 /// Balance commitment for wallet state
@@ -314,6 +689,44 @@ impl BalanceCommitment {
     }
 }
 
+/// A batch of balance commitments (e.g. every output of a transaction)
+/// proved in range with a single aggregated Bulletproof, instead of one
+/// `RangeProof` per commitment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregatedBalanceCommitment {
+    /// The Pedersen commitment to each balance, in the order they were proved.
+    pub commitments: Vec<Commitment>,
+
+    /// One aggregated range proof covering every commitment above.
+    pub range_proof: RangeProof,
+}
+
+impl AggregatedBalanceCommitment {
+    /// Commit to and prove the range of every balance in `balances` with a
+    /// single aggregated proof. `balances.len()` must make `bit_length *
+    /// balances.len()` a power of two (see [`RangeProof::prove_aggregated`]).
+    pub fn new(balances: &[u64], blindings: &[Scalar], bit_length: usize) -> Result<Self> {
+        let pedersen = PedersenCommitment::new();
+        let commitments = balances
+            .iter()
+            .zip(blindings.iter())
+            .map(|(&value, blinding)| pedersen.commit(value, blinding))
+            .collect();
+
+        let range_proof = RangeProof::prove_aggregated(balances, blindings, bit_length)?;
+
+        Ok(AggregatedBalanceCommitment {
+            commitments,
+            range_proof,
+        })
+    }
+
+    /// Verify every committed balance is in range with one combined check.
+    pub fn verify(&self) -> bool {
+        self.range_proof.verify_aggregated(&self.commitments)
+    }
+}
+
 // Serialization helper for RistrettoPoint
 mod ristretto_serde {
     use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
@@ -417,10 +830,124 @@ mod tests {
         let blinding = random_scalar();
         
         let balance_commitment = BalanceCommitment::new(balance, &blinding).unwrap();
-        
+
         assert!(balance_commitment.verify());
     }
-    
+
+    #[test]
+    fn test_aggregated_range_proof() {
+        let values = [10u64, 200u64, 3_000u64, 40_000u64];
+        let blindings: Vec<Scalar> = (0..values.len()).map(|_| random_scalar()).collect();
+
+        let aggregated = AggregatedBalanceCommitment::new(&values, &blindings, 16).unwrap();
+        assert!(aggregated.verify());
+    }
+
+    #[test]
+    fn test_verify_batch_checks_each_independent_proof() {
+        let pedersen = PedersenCommitment::new();
+        let (commitment_a, blinding_a) = pedersen.commit_with_random_blinding(5);
+        let (commitment_b, blinding_b) = pedersen.commit_with_random_blinding(9);
+
+        let proof_a = RangeProof::prove(5, &blinding_a, 64).unwrap();
+        let proof_b = RangeProof::prove(9, &blinding_b, 64).unwrap();
+
+        assert!(RangeProof::verify_batch(
+            &[proof_a.clone(), proof_b.clone()],
+            &[commitment_a.clone(), commitment_b.clone()],
+        ));
+
+        // A wrong commitment anywhere in the batch fails the whole batch.
+        let (other_commitment, _) = pedersen.commit_with_random_blinding(9);
+        assert!(!RangeProof::verify_batch(
+            &[proof_a, proof_b],
+            &[commitment_a, other_commitment],
+        ));
+    }
+
+    #[test]
+    fn test_aggregated_rejects_non_power_of_two_batch() {
+        let values = [1u64, 2u64, 3u64];
+        let blindings: Vec<Scalar> = (0..values.len()).map(|_| random_scalar()).collect();
+
+        assert!(RangeProof::prove_aggregated(&values, &blindings, 32).is_err());
+    }
+
+    #[test]
+    fn test_rewindable_proof_recovers_opening() {
+        let value = 42_000u64;
+        let blinding = random_scalar();
+        let rewind_nonce = b"shared-rewind-nonce";
+        let key_separator = b"SafeMask-Rewind-v1";
+
+        let proof =
+            RangeProof::prove_rewindable(value, &blinding, 64, rewind_nonce, key_separator)
+                .unwrap();
+
+        let (recovered_value, recovered_blinding) =
+            proof.rewind(rewind_nonce, key_separator).unwrap();
+
+        assert_eq!(recovered_value, value);
+        assert_eq!(recovered_blinding, blinding);
+
+        let pedersen = PedersenCommitment::new();
+        let commitment = pedersen.commit(value, &blinding);
+        assert!(proof.verify(&commitment));
+    }
+
+    #[test]
+    fn test_rewind_rejects_wrong_nonce() {
+        let value = 7u64;
+        let blinding = random_scalar();
+
+        let proof =
+            RangeProof::prove_rewindable(value, &blinding, 8, b"right-nonce", b"sep").unwrap();
+
+        assert!(proof.rewind(b"wrong-nonce", b"sep").is_err());
+    }
+
+    #[test]
+    fn test_rewind_fails_without_trailer() {
+        let value = 7u64;
+        let blinding = random_scalar();
+
+        let proof = RangeProof::prove(value, &blinding, 8).unwrap();
+        assert!(proof.rewind(b"any-nonce", b"sep").is_err());
+    }
+
+    #[test]
+    fn test_bit_length_proof_roundtrip() {
+        let value = 999u64;
+        let blinding = random_scalar();
+        let domain = b"SafeMask-per-tx-spend-limit";
+
+        let proof = RangeProof::prove_bit_length(value, &blinding, 1_000, domain).unwrap();
+
+        let pedersen = PedersenCommitment::new();
+        let commitment = pedersen.commit(value, &blinding);
+
+        assert!(proof.verify_bit_length(&commitment, 1_000, domain));
+    }
+
+    #[test]
+    fn test_bit_length_proof_rejects_value_at_or_above_bound() {
+        let blinding = random_scalar();
+        assert!(RangeProof::prove_bit_length(1_000, &blinding, 1_000, b"domain").is_err());
+    }
+
+    #[test]
+    fn test_bit_length_proof_rejects_wrong_domain() {
+        let value = 5u64;
+        let blinding = random_scalar();
+
+        let proof = RangeProof::prove_bit_length(value, &blinding, 100, b"domain-a").unwrap();
+
+        let pedersen = PedersenCommitment::new();
+        let commitment = pedersen.commit(value, &blinding);
+
+        assert!(!proof.verify_bit_length(&commitment, 100, b"domain-b"));
+    }
+
     #[test]
     fn test_transaction_balance_equation() {
         let pedersen = PedersenCommitment::new();
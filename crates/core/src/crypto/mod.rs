@@ -0,0 +1,4 @@
+//! Cryptographic building blocks, grouped separately from the higher-level
+//! wallet/transaction modules in [`crate`].
+
+pub mod primitives;
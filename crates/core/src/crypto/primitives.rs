@@ -206,6 +206,12 @@ pub mod stealth {
     }
     
     impl StealthKeypair {
+        /// Encode this keypair's public half as a single Bech32m unified
+        /// stealth address (see [`crate::bech32`]).
+        pub fn encoded_address(&self) -> Result<String> {
+            crate::bech32::encode_stealth_address(&self.view_public, &self.spend_public)
+        }
+
         /// Generate a new stealth keypair
         pub fn generate() -> Self {
             let spend_private = Scalar::random(&mut rand::thread_rng());
@@ -243,7 +249,21 @@ pub mod stealth {
             
             (ephemeral_public, stealth_public, ephemeral_private)
         }
-        
+
+        /// As [`Self::derive_stealth_address`], but takes the recipient's
+        /// single encoded (Bech32m) unified stealth address instead of the
+        /// two raw public keys.
+        pub fn derive_stealth_address_from_encoded(
+            recipient_address: &str,
+        ) -> Result<(RistrettoPoint, RistrettoPoint, Scalar)> {
+            let (recipient_view_public, recipient_spend_public) =
+                crate::bech32::decode_stealth_address(recipient_address)?;
+            Ok(Self::derive_stealth_address(
+                &recipient_spend_public,
+                &recipient_view_public,
+            ))
+        }
+
         /// Scan for owned stealth addresses
         pub fn scan_stealth_address(
             &self,
@@ -0,0 +1,164 @@
+//! Twisted ElGamal Encryption of Committed Amounts
+//!
+//! Pairs every [`PedersenCommitment`] with a decryptable ciphertext, the way
+//! the Solana zk-token-sdk's `ElGamalCiphertext` does: the commitment
+//! `C = v·G + r·H` is unchanged, but the same blinding factor `r` also
+//! produces a "decrypt handle" `D = r·P` under a recipient's (or auditor's)
+//! public key `P = sk·H`. Because both pieces share the generator `H`, the
+//! owner of `sk` can peel the commitment back to `v·G`:
+//!
+//! ```text
+//! D · sk⁻¹ = r·sk·H·sk⁻¹ = r·H
+//! C - D·sk⁻¹ = v·G + r·H - r·H = v·G
+//! ```
+//!
+//! `v·G` only reveals `v` up to solving a discrete log, which is recovered
+//! here with a baby-step/giant-step table over a bounded range (see
+//! [`MAX_DECRYPTABLE_BITS`]) since real wallet amounts fit comfortably
+//! within it.
+
+use crate::commitments::{Commitment, PedersenCommitment};
+use crate::{CoreError, Result};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::Identity};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Largest amount `decrypt` will recover, as a power of two. Matches the
+/// zk-token-sdk's u32-bounded decryptable balance: wide enough for any
+/// realistic wallet amount while keeping the baby-step/giant-step table
+/// small enough to build on demand.
+pub const MAX_DECRYPTABLE_BITS: u32 = 32;
+
+/// A twisted-ElGamal keypair, scoped to the [`PedersenCommitment`] `H`
+/// generator so decrypt handles can be peeled off of ordinary commitments.
+pub struct ElGamalKeypair {
+    secret: Scalar,
+    /// Public key: `P = sk·H`.
+    pub public: ElGamalPubkey,
+}
+
+/// The public half of an [`ElGamalKeypair`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElGamalPubkey(pub RistrettoPoint);
+
+/// The decrypt handle half of a ciphertext: `D = r·P`, alongside the
+/// [`Commitment`] `C = v·G + r·H` produced with the same blinding `r`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecryptHandle(pub RistrettoPoint);
+
+impl ElGamalKeypair {
+    /// Generate a new keypair.
+    pub fn generate() -> Self {
+        let secret = Scalar::random(&mut rand::thread_rng());
+        let public = ElGamalPubkey(secret * PedersenCommitment::new().h);
+        ElGamalKeypair { secret, public }
+    }
+
+    /// Decrypt a `(commitment, handle)` pair produced by [`encrypt`] under
+    /// this keypair's public key, recovering the committed amount.
+    ///
+    /// Returns [`CoreError::Crypto`] if no value within
+    /// `[0, 2^MAX_DECRYPTABLE_BITS)` opens the commitment.
+    pub fn decrypt(&self, commitment: &Commitment, handle: &DecryptHandle) -> Result<u64> {
+        let sk_inv = self.secret.invert();
+        let value_point = commitment.point - handle.0 * sk_inv;
+        discrete_log(&value_point)
+    }
+}
+
+/// Encrypt `value` for `pubkey`, returning the Pedersen commitment and its
+/// matching decrypt handle under a fresh random blinding factor.
+pub fn encrypt(value: u64, pubkey: &ElGamalPubkey) -> (Commitment, DecryptHandle) {
+    let pedersen = PedersenCommitment::new();
+    let (commitment, blinding) = pedersen.commit_with_random_blinding(value);
+    let handle = DecryptHandle(blinding * pubkey.0);
+    (commitment, handle)
+}
+
+/// Homomorphically add two decrypt handles, mirroring
+/// [`PedersenCommitment::add_commitments`] so an encrypted balance stays
+/// decryptable after the commitments it pairs with are summed.
+pub fn add_handles(h1: &DecryptHandle, h2: &DecryptHandle) -> DecryptHandle {
+    DecryptHandle(h1.0 + h2.0)
+}
+
+/// Subtract two decrypt handles, mirroring
+/// [`PedersenCommitment::subtract_commitments`].
+pub fn subtract_handles(h1: &DecryptHandle, h2: &DecryptHandle) -> DecryptHandle {
+    DecryptHandle(h1.0 - h2.0)
+}
+
+/// Baby-step table mapping `j·G` to `j` for `j` in `[0, 2^16)`, built once
+/// and reused by every [`discrete_log`] call.
+fn baby_steps() -> &'static HashMap<[u8; 32], u32> {
+    static TABLE: OnceLock<HashMap<[u8; 32], u32>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let g = PedersenCommitment::new().g;
+        let step_count = 1u32 << (MAX_DECRYPTABLE_BITS / 2);
+        let mut table = HashMap::with_capacity(step_count as usize);
+        let mut point = RistrettoPoint::identity();
+        for j in 0..step_count {
+            table.insert(point.compress().to_bytes(), j);
+            point += g;
+        }
+        table
+    })
+}
+
+/// Recover `v` from `v·G` for `v` in `[0, 2^MAX_DECRYPTABLE_BITS)` using
+/// baby-step/giant-step.
+fn discrete_log(target: &RistrettoPoint) -> Result<u64> {
+    let g = PedersenCommitment::new().g;
+    let step_count = 1u32 << (MAX_DECRYPTABLE_BITS / 2);
+    let table = baby_steps();
+
+    let giant_stride = -(g * Scalar::from(step_count));
+    let mut giant = *target;
+    for i in 0..step_count {
+        if let Some(&j) = table.get(&giant.compress().to_bytes()) {
+            return Ok(i as u64 * step_count as u64 + j as u64);
+        }
+        giant += giant_stride;
+    }
+
+    Err(CoreError::Crypto(
+        "value exceeds the decryptable range".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let keypair = ElGamalKeypair::generate();
+        let (commitment, handle) = encrypt(12345, &keypair.public);
+
+        let recovered = keypair.decrypt(&commitment, &handle).unwrap();
+        assert_eq!(recovered, 12345);
+    }
+
+    #[test]
+    fn test_wrong_keypair_fails_to_decrypt() {
+        let keypair = ElGamalKeypair::generate();
+        let other = ElGamalKeypair::generate();
+        let (commitment, handle) = encrypt(777, &keypair.public);
+
+        let recovered = other.decrypt(&commitment, &handle);
+        assert!(recovered.is_err() || recovered.unwrap() != 777);
+    }
+
+    #[test]
+    fn test_handle_addition_matches_commitment_addition() {
+        let keypair = ElGamalKeypair::generate();
+        let (c1, h1) = encrypt(100, &keypair.public);
+        let (c2, h2) = encrypt(250, &keypair.public);
+
+        let c_sum = PedersenCommitment::add_commitments(&c1, &c2);
+        let h_sum = add_handles(&h1, &h2);
+
+        let recovered = keypair.decrypt(&c_sum, &h_sum).unwrap();
+        assert_eq!(recovered, 350);
+    }
+}
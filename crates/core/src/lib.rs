@@ -18,17 +18,33 @@
 //! Commitments  Storage        ZK Proofs
 //! ```
 
+pub mod bech32;
 pub mod commitments;
 pub mod crypto;
+pub mod elgamal;
 pub mod key_manager;
+pub mod merkle;
+pub mod note_encryption;
+pub mod nullifier;
+pub mod scanner;
 pub mod storage;
 pub mod transaction_builder;
+pub mod validity;
+pub mod wallet;
 pub mod wallet_state;
 
 // Re-exports
-pub use commitments::{PedersenCommitment, RangeProof};
+pub use bech32::{decode_stealth_address, encode_stealth_address};
+pub use commitments::{AggregatedBalanceCommitment, PedersenCommitment, RangeProof};
+pub use elgamal::{DecryptHandle, ElGamalKeypair, ElGamalPubkey};
 pub use key_manager::{KeyManager, Account, AccountDerivation};
+pub use merkle::{CommitmentTree, MerklePath};
+pub use note_encryption::NotePlaintext;
+pub use nullifier::{compute_nullifier, NullifierSet};
+pub use scanner::{Note, NoteStore, ScanResult};
 pub use transaction_builder::{TransactionBuilder, PrivateTransaction};
+pub use validity::{BalanceProof, EqualityProof, Transaction};
+pub use wallet::SafeMaskWallet;
 pub use wallet_state::{WalletState, EncryptedState};
 
 use thiserror::Error;
@@ -0,0 +1,320 @@
+//! Incremental Merkle Tree
+//!
+//! Append-only commitment accumulator matching the `CommitmentTree` /
+//! `IncrementalWitness` pattern from the Zcash wallet: every confidential
+//! output's Pedersen commitment is appended as a leaf, and a spender proves
+//! membership by presenting an authentication path rooted at a known
+//! `anchor()` rather than revealing which leaf they spent.
+//!
+//! Appending and reading the current root only touch the O(depth) frontier.
+//! Retaining every leaf to answer [`CommitmentTree::witness`] for an
+//! arbitrary past index is the one place this tree's storage is O(n) rather
+//! than O(depth): unlike Zcash's `IncrementalWitness`, which a wallet tracks
+//! and advances per-note as new leaves arrive, `witness()` here takes a bare
+//! `leaf_index` and must be able to answer for any of them on demand, which
+//! means the underlying leaves have to still be around to replay. Callers
+//! that care about bounded memory (e.g. a long-running node that doesn't
+//! need witnesses for most of its history) should track per-note witnesses
+//! themselves instead of retaining this tree's leaf log.
+
+use crate::crypto::primitives::blake2b;
+use crate::{CoreError, Result};
+
+/// Depth of the tree. 2^32 leaves of headroom mirrors the size Zcash's
+/// Sapling commitment tree uses.
+pub const TREE_DEPTH: usize = 32;
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(64);
+    preimage.extend_from_slice(left);
+    preimage.extend_from_slice(right);
+    let hash = blake2b(&preimage);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hash[..32]);
+    out
+}
+
+/// The empty-subtree hash at each level, so gaps in a partially-filled tree
+/// don't need to be materialized.
+fn empty_roots() -> [[u8; 32]; TREE_DEPTH + 1] {
+    let mut roots = [[0u8; 32]; TREE_DEPTH + 1];
+    roots[0] = {
+        let hash = blake2b(b"SafeMask-Merkle-Empty-Leaf");
+        let mut leaf = [0u8; 32];
+        leaf.copy_from_slice(&hash[..32]);
+        leaf
+    };
+    for level in 1..=TREE_DEPTH {
+        roots[level] = node_hash(&roots[level - 1], &roots[level - 1]);
+    }
+    roots
+}
+
+/// An authentication path from a leaf to the tree root.
+#[derive(Clone, Debug)]
+pub struct MerklePath {
+    /// Sibling hash at each level, from leaf to root.
+    pub siblings: [[u8; 32]; TREE_DEPTH],
+    /// The leaf's position, whose bits select left/right at each level.
+    pub leaf_index: u64,
+}
+
+impl MerklePath {
+    /// Recompute the root implied by this path for a given leaf.
+    pub fn root(&self, leaf: &[u8; 32]) -> [u8; 32] {
+        let mut current = *leaf;
+        let mut index = self.leaf_index;
+        for sibling in &self.siblings {
+            current = if index & 1 == 0 {
+                node_hash(&current, sibling)
+            } else {
+                node_hash(sibling, &current)
+            };
+            index >>= 1;
+        }
+        current
+    }
+
+    /// Serialize as `leaf_index (8 bytes LE) || siblings (32 bytes each)`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(8 + TREE_DEPTH * 32);
+        out.extend_from_slice(&self.leaf_index.to_le_bytes());
+        for sibling in &self.siblings {
+            out.extend_from_slice(sibling);
+        }
+        out
+    }
+}
+
+/// One frontier slot: the left-hand hash retained at a given level of the
+/// rightmost append path.
+#[derive(Clone, Copy, Default)]
+struct FrontierSlot {
+    hash: [u8; 32],
+    filled: bool,
+}
+
+/// A checkpoint the tree can be rewound to after a reorg.
+#[derive(Clone)]
+pub struct Checkpoint {
+    frontier: [FrontierSlot; TREE_DEPTH],
+    root: [u8; 32],
+    leaf_count: usize,
+}
+
+/// Fixed-depth incremental Merkle tree over output commitments.
+///
+/// `frontier` is the only state `append`/`root` touch, and stays O(depth).
+/// `leaves` exists purely so `witness()` can answer for any past index; it
+/// grows with every append and is this type's one O(n) cost (see module
+/// docs).
+#[derive(Clone)]
+pub struct CommitmentTree {
+    frontier: [FrontierSlot; TREE_DEPTH],
+    root: [u8; 32],
+    leaves: Vec<[u8; 32]>,
+    empty_roots: [[u8; 32]; TREE_DEPTH + 1],
+}
+
+impl CommitmentTree {
+    pub fn new() -> Self {
+        let empty_roots = empty_roots();
+        CommitmentTree {
+            frontier: [FrontierSlot::default(); TREE_DEPTH],
+            root: empty_roots[TREE_DEPTH],
+            leaves: Vec::new(),
+            empty_roots,
+        }
+    }
+
+    /// Number of leaves appended so far.
+    pub fn len(&self) -> u64 {
+        self.leaves.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Append a commitment as the next leaf, returning its index.
+    pub fn append(&mut self, commitment: &[u8; 32]) -> Result<u64> {
+        if self.leaves.len() as u64 >= 1u64 << TREE_DEPTH {
+            return Err(CoreError::Commitment("commitment tree is full".into()));
+        }
+
+        let leaf_index = self.leaves.len() as u64;
+        let mut index = leaf_index;
+        let mut current = *commitment;
+
+        for level in 0..TREE_DEPTH {
+            let slot = &mut self.frontier[level];
+            if index % 2 == 0 {
+                // Left child: stash it, combine with the empty sibling for
+                // the running root, and wait for a right sibling to arrive.
+                let right = self.empty_roots[level];
+                *slot = FrontierSlot {
+                    hash: current,
+                    filled: true,
+                };
+                current = node_hash(&current, &right);
+            } else {
+                // Right child: combine with the stashed left sibling.
+                let left = if slot.filled {
+                    slot.hash
+                } else {
+                    self.empty_roots[level]
+                };
+                current = node_hash(&left, &current);
+            }
+            index /= 2;
+        }
+
+        self.root = current;
+        self.leaves.push(*commitment);
+        Ok(leaf_index)
+    }
+
+    /// Current root (anchor) of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Produce the authentication path for a previously-appended leaf.
+    pub fn witness(&self, leaf_index: u64) -> Result<MerklePath> {
+        if leaf_index >= self.leaves.len() as u64 {
+            return Err(CoreError::Commitment(format!(
+                "unknown leaf index {}",
+                leaf_index
+            )));
+        }
+
+        let mut level = self.leaves.clone();
+        let mut siblings = [[0u8; 32]; TREE_DEPTH];
+        let mut index = leaf_index as usize;
+
+        for (depth, sibling_slot) in siblings.iter_mut().enumerate() {
+            let sibling_index = index ^ 1;
+            *sibling_slot = level
+                .get(sibling_index)
+                .copied()
+                .unwrap_or(self.empty_roots[depth]);
+
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            let mut i = 0;
+            while i < level.len() {
+                let left = level[i];
+                let right = level.get(i + 1).copied().unwrap_or(self.empty_roots[depth]);
+                next.push(node_hash(&left, &right));
+                i += 2;
+            }
+            level = next;
+            index /= 2;
+        }
+
+        Ok(MerklePath {
+            siblings,
+            leaf_index,
+        })
+    }
+
+    /// Snapshot the current state so it can be restored on reorg.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            frontier: self.frontier,
+            root: self.root,
+            leaf_count: self.leaves.len(),
+        }
+    }
+
+    /// Restore the tree to a prior checkpoint, discarding any leaves
+    /// appended since then.
+    pub fn rewind(&mut self, checkpoint: &Checkpoint) {
+        self.frontier = checkpoint.frontier;
+        self.root = checkpoint.root;
+        self.leaves.truncate(checkpoint.leaf_count);
+    }
+}
+
+impl Default for CommitmentTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(n: u8) -> [u8; 32] {
+        let mut l = [0u8; 32];
+        l[0] = n;
+        l
+    }
+
+    #[test]
+    fn test_append_and_witness_roundtrip() {
+        let mut tree = CommitmentTree::new();
+        let idx0 = tree.append(&leaf(1)).unwrap();
+        let idx1 = tree.append(&leaf(2)).unwrap();
+        let idx2 = tree.append(&leaf(3)).unwrap();
+
+        let root = tree.root();
+
+        let path0 = tree.witness(idx0).unwrap();
+        assert_eq!(path0.root(&leaf(1)), root);
+
+        let path1 = tree.witness(idx1).unwrap();
+        assert_eq!(path1.root(&leaf(2)), root);
+
+        let path2 = tree.witness(idx2).unwrap();
+        assert_eq!(path2.root(&leaf(3)), root);
+    }
+
+    #[test]
+    fn test_witness_rejects_wrong_leaf() {
+        let mut tree = CommitmentTree::new();
+        let idx = tree.append(&leaf(9)).unwrap();
+        let path = tree.witness(idx).unwrap();
+
+        assert_ne!(path.root(&leaf(10)), tree.root());
+    }
+
+    #[test]
+    fn test_witness_updates_as_tree_grows() {
+        // The authentication path for an early leaf changes once its
+        // sibling subtree is no longer empty.
+        let mut tree = CommitmentTree::new();
+        let idx0 = tree.append(&leaf(1)).unwrap();
+        let path_before = tree.witness(idx0).unwrap();
+
+        tree.append(&leaf(2)).unwrap();
+        let path_after = tree.witness(idx0).unwrap();
+
+        assert_ne!(path_before.siblings[0], path_after.siblings[0]);
+        assert_eq!(path_after.root(&leaf(1)), tree.root());
+    }
+
+    #[test]
+    fn test_rewind_restores_prior_state() {
+        let mut tree = CommitmentTree::new();
+        tree.append(&leaf(1)).unwrap();
+        let checkpoint = tree.checkpoint();
+        let root_before = tree.root();
+
+        tree.append(&leaf(2)).unwrap();
+        tree.append(&leaf(3)).unwrap();
+        assert_ne!(tree.root(), root_before);
+
+        tree.rewind(&checkpoint);
+        assert_eq!(tree.root(), root_before);
+        assert_eq!(tree.len(), 1);
+        assert!(tree.witness(1).is_err());
+    }
+
+    #[test]
+    fn test_empty_tree_root_is_stable() {
+        let tree = CommitmentTree::new();
+        let other = CommitmentTree::new();
+        assert_eq!(tree.root(), other.root());
+    }
+}
@@ -0,0 +1,186 @@
+//! Note Encryption
+//!
+//! Encrypted memo channel for confidential outputs, modeled on Zcash's
+//! `try_sapling_note_decryption`. The shared secret already computed while
+//! deriving a stealth address (`σ = r·V = v·R`) doubles as the key-agreement
+//! input for a symmetric note ciphertext, so a recipient who can scan for an
+//! output can also recover its memo, value and blinding factor.
+//!
+//! # Wire format
+//!
+//! ```text
+//! plaintext = memo (512 bytes, zero-padded) || value (8 bytes LE) || blinding (32 bytes)
+//! k         = blake2b(σ.compress())[..32]
+//! note_ciphertext = ChaCha20Cipher(k).encrypt(plaintext)
+//! ```
+
+use crate::crypto::primitives::{blake2b, ChaCha20Cipher};
+use crate::{CoreError, Result};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+
+/// Fixed length of the zero-padded memo field, matching Zcash's memo size.
+pub const MEMO_LEN: usize = 512;
+
+/// Plaintext note contents recovered from a successful decryption.
+#[derive(Clone)]
+pub struct NotePlaintext {
+    /// Zero-padded memo bytes.
+    pub memo: [u8; MEMO_LEN],
+    /// Committed value.
+    pub value: u64,
+    /// Blinding factor used in the output's Pedersen commitment.
+    pub blinding: Scalar,
+}
+
+/// Derive the symmetric note-encryption key from a note's shared secret.
+///
+/// `k = blake2b(σ.compress())[..32]`
+fn derive_note_key(shared_secret: &RistrettoPoint) -> [u8; 32] {
+    let hash = blake2b(shared_secret.compress().as_bytes());
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hash[..32]);
+    key
+}
+
+fn encode_plaintext(memo: &[u8], value: u64, blinding: &Scalar) -> Result<[u8; MEMO_LEN + 8 + 32]> {
+    if memo.len() > MEMO_LEN {
+        return Err(CoreError::InvalidParameter(format!(
+            "memo exceeds {} bytes",
+            MEMO_LEN
+        )));
+    }
+
+    let mut out = [0u8; MEMO_LEN + 8 + 32];
+    out[..memo.len()].copy_from_slice(memo);
+    out[MEMO_LEN..MEMO_LEN + 8].copy_from_slice(&value.to_le_bytes());
+    out[MEMO_LEN + 8..].copy_from_slice(blinding.as_bytes());
+    Ok(out)
+}
+
+fn decode_plaintext(bytes: &[u8]) -> Result<NotePlaintext> {
+    if bytes.len() != MEMO_LEN + 8 + 32 {
+        return Err(CoreError::Crypto("malformed note plaintext".into()));
+    }
+
+    let mut memo = [0u8; MEMO_LEN];
+    memo.copy_from_slice(&bytes[..MEMO_LEN]);
+
+    let mut value_bytes = [0u8; 8];
+    value_bytes.copy_from_slice(&bytes[MEMO_LEN..MEMO_LEN + 8]);
+    let value = u64::from_le_bytes(value_bytes);
+
+    let mut blinding_bytes = [0u8; 32];
+    blinding_bytes.copy_from_slice(&bytes[MEMO_LEN + 8..]);
+    let blinding = Scalar::from_canonical_bytes(blinding_bytes)
+        .into_option()
+        .ok_or_else(|| CoreError::Crypto("invalid blinding factor in note".into()))?;
+
+    Ok(NotePlaintext {
+        memo,
+        value,
+        blinding,
+    })
+}
+
+/// Encrypt a note for the recipient, given the shared secret `σ` produced
+/// alongside the one-time stealth address (either `r·V` on the sender side
+/// or `v·R` on the recipient side — both yield the same point).
+pub fn encrypt_note(
+    shared_secret: &RistrettoPoint,
+    memo: &[u8],
+    value: u64,
+    blinding: &Scalar,
+) -> Result<Vec<u8>> {
+    let plaintext = encode_plaintext(memo, value, blinding)?;
+    let key = derive_note_key(shared_secret);
+    ChaCha20Cipher::new(&key).encrypt(&plaintext)
+}
+
+/// Attempt to decrypt a note ciphertext with a shared secret.
+///
+/// Returns `Ok(None)` if decryption fails, since trial decryption is the
+/// mechanism by which a scanner determines note ownership rather than an
+/// error condition.
+pub fn try_decrypt_note(
+    shared_secret: &RistrettoPoint,
+    ciphertext: &[u8],
+) -> Result<Option<NotePlaintext>> {
+    let key = derive_note_key(shared_secret);
+    match ChaCha20Cipher::new(&key).decrypt(ciphertext) {
+        Ok(plaintext) => decode_plaintext(&plaintext).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Recipient-side scan: recompute `σ = v·R` from the view private key and the
+/// output's ephemeral public key, then attempt decryption.
+pub fn scan_note(
+    view_private: &Scalar,
+    ephemeral_public: &RistrettoPoint,
+    ciphertext: &[u8],
+) -> Result<Option<NotePlaintext>> {
+    let shared_secret = view_private * ephemeral_public;
+    try_decrypt_note(&shared_secret, ciphertext)
+}
+
+/// Sender-side recovery: recompute `σ = r·V` from the ephemeral private key
+/// used when building the output and the recipient's view public key, so the
+/// sender can read back their own outgoing memos.
+pub fn recover_outgoing_note(
+    ephemeral_private: &Scalar,
+    recipient_view_public: &RistrettoPoint,
+    ciphertext: &[u8],
+) -> Result<Option<NotePlaintext>> {
+    let shared_secret = ephemeral_private * recipient_view_public;
+    try_decrypt_note(&shared_secret, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as G;
+
+    #[test]
+    fn test_recipient_and_sender_recover_same_note() {
+        let view_private = Scalar::random(&mut rand::thread_rng());
+        let view_public = view_private * G;
+
+        let ephemeral_private = Scalar::random(&mut rand::thread_rng());
+        let ephemeral_public = ephemeral_private * G;
+
+        let value = 42_000u64;
+        let blinding = Scalar::random(&mut rand::thread_rng());
+        let memo = b"for the coffee";
+
+        let shared_secret_sender = ephemeral_private * view_public;
+        let ciphertext = encrypt_note(&shared_secret_sender, memo, value, &blinding).unwrap();
+
+        let recovered = scan_note(&view_private, &ephemeral_public, &ciphertext)
+            .unwrap()
+            .expect("recipient should decrypt the note");
+        assert_eq!(recovered.value, value);
+        assert_eq!(recovered.blinding, blinding);
+        assert_eq!(&recovered.memo[..memo.len()], memo);
+
+        let sender_side = recover_outgoing_note(&ephemeral_private, &view_public, &ciphertext)
+            .unwrap()
+            .expect("sender should recover their own outgoing note");
+        assert_eq!(sender_side.value, value);
+        assert_eq!(sender_side.blinding, blinding);
+    }
+
+    #[test]
+    fn test_wrong_view_key_does_not_decrypt() {
+        let ephemeral_private = Scalar::random(&mut rand::thread_rng());
+        let view_private = Scalar::random(&mut rand::thread_rng());
+        let view_public = view_private * G;
+
+        let shared_secret = ephemeral_private * view_public;
+        let ciphertext = encrypt_note(&shared_secret, b"memo", 10, &Scalar::ONE).unwrap();
+
+        let wrong_view_private = Scalar::random(&mut rand::thread_rng());
+        let ephemeral_public = ephemeral_private * G;
+        let result = scan_note(&wrong_view_private, &ephemeral_public, &ciphertext).unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,86 @@
+//! Nullifiers and Double-Spend Tracking
+//!
+//! A confidential note's one-time spend key `p` is only known to whoever
+//! can scan and spend it, so `nf = blake2b(p || commitment)` is a value a
+//! spender can reveal without leaking which note it came from, while still
+//! letting anyone else recognize a repeat reveal as a double-spend attempt.
+//! Mirrors the spent-note bookkeeping in the Zcash wallet backend.
+
+use crate::crypto::primitives::blake2b;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::HashSet;
+
+/// Derive the nullifier for a note given its one-time spend key and the
+/// commitment it was created under.
+///
+/// `nf = blake2b(p.as_bytes() || commitment)[..32]`
+pub fn compute_nullifier(stealth_private: &Scalar, commitment: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(64);
+    preimage.extend_from_slice(stealth_private.as_bytes());
+    preimage.extend_from_slice(commitment);
+
+    let hash = blake2b(&preimage);
+    let mut nullifier = [0u8; 32];
+    nullifier.copy_from_slice(&hash[..32]);
+    nullifier
+}
+
+/// Tracks every nullifier that has been accepted on-chain so a spend can be
+/// rejected the second time it's presented.
+#[derive(Default)]
+pub struct NullifierSet {
+    spent: HashSet<[u8; 32]>,
+}
+
+impl NullifierSet {
+    pub fn new() -> Self {
+        Self {
+            spent: HashSet::new(),
+        }
+    }
+
+    /// Record a nullifier as spent. Returns `false` if it was already
+    /// present (i.e. this would be a double-spend).
+    pub fn insert(&mut self, nullifier: [u8; 32]) -> bool {
+        self.spent.insert(nullifier)
+    }
+
+    /// Whether a nullifier has already been spent.
+    pub fn contains(&self, nullifier: &[u8; 32]) -> bool {
+        self.spent.contains(nullifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullifier_is_deterministic() {
+        let spend_key = Scalar::from(7u64);
+        let commitment = [9u8; 32];
+
+        let nf1 = compute_nullifier(&spend_key, &commitment);
+        let nf2 = compute_nullifier(&spend_key, &commitment);
+        assert_eq!(nf1, nf2);
+    }
+
+    #[test]
+    fn test_nullifier_differs_per_note() {
+        let spend_key = Scalar::from(7u64);
+        let nf_a = compute_nullifier(&spend_key, &[1u8; 32]);
+        let nf_b = compute_nullifier(&spend_key, &[2u8; 32]);
+        assert_ne!(nf_a, nf_b);
+    }
+
+    #[test]
+    fn test_nullifier_set_rejects_replay() {
+        let mut set = NullifierSet::new();
+        let nullifier = compute_nullifier(&Scalar::from(1u64), &[0u8; 32]);
+
+        assert!(!set.contains(&nullifier));
+        assert!(set.insert(nullifier));
+        assert!(set.contains(&nullifier));
+        assert!(!set.insert(nullifier));
+    }
+}
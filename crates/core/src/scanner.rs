@@ -0,0 +1,284 @@
+//! Chain Scanner
+//!
+//! Drives [`crate::crypto::primitives::stealth::StealthKeypair::scan_stealth_address`]
+//! over a stream of transactions so a wallet can discover incoming funds.
+//! Modeled on `zcash_client_backend`'s `scan_block`: outputs are processed in
+//! order, detected notes accumulate into a per-wallet store, and each scan
+//! call returns only what's new so a mobile client can sync incrementally.
+
+use crate::commitments::Commitment;
+use crate::crypto::primitives::stealth::StealthKeypair;
+use crate::note_encryption::{self, NotePlaintext};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+
+/// A single candidate output to trial-decrypt, carrying everything the
+/// scanner needs regardless of which wire format produced it.
+#[derive(Clone)]
+pub struct ScanOutput {
+    pub ephemeral_public: RistrettoPoint,
+    pub stealth_public: RistrettoPoint,
+    pub commitment: Commitment,
+    pub encrypted_note: Vec<u8>,
+    /// Position of this output's commitment in the shared commitment tree
+    /// (see [`crate::merkle::CommitmentTree`]), so a detected note can later
+    /// be proven via a spend witness.
+    pub leaf_index: u64,
+}
+
+/// A transaction's outputs, indexed by position in the scanned batch.
+#[derive(Clone)]
+pub struct ScanTransaction {
+    pub outputs: Vec<ScanOutput>,
+}
+
+/// A note the wallet owns: the one-time spend key plus everything recovered
+/// from the note ciphertext, located by its position in the scanned chain.
+#[derive(Clone)]
+pub struct Note {
+    pub stealth_private: Scalar,
+    pub value: u64,
+    pub commitment: Commitment,
+    pub tx_index: usize,
+    pub output_index: usize,
+    /// Leaf index of this note's commitment in the commitment tree, used to
+    /// fetch a spend witness when the note is later spent.
+    pub leaf_index: u64,
+}
+
+/// Per-wallet accumulator of detected notes and the running balance.
+#[derive(Default)]
+pub struct NoteStore {
+    notes: Vec<Note>,
+}
+
+impl NoteStore {
+    pub fn new() -> Self {
+        Self { notes: Vec::new() }
+    }
+
+    /// All notes detected so far.
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Sum of every detected note's value.
+    pub fn balance(&self) -> u64 {
+        self.notes.iter().map(|n| n.value).sum()
+    }
+
+    fn record(&mut self, note: Note) {
+        self.notes.push(note);
+    }
+
+    /// Remove the note at `leaf_index`, e.g. once it's been selected to fund
+    /// a spend and shouldn't be offered again before the spend confirms.
+    pub fn remove_by_leaf_index(&mut self, leaf_index: u64) -> Option<Note> {
+        let position = self.notes.iter().position(|note| note.leaf_index == leaf_index)?;
+        Some(self.notes.remove(position))
+    }
+}
+
+/// Result of a single scan pass: the notes newly discovered in this batch,
+/// and the wallet's balance after applying them.
+pub struct ScanResult {
+    pub new_notes: Vec<Note>,
+    pub balance: u64,
+}
+
+/// Scan a batch of transactions for outputs owned by `keypair`, appending
+/// any newly-discovered notes to `store` and returning them alongside the
+/// updated balance.
+///
+/// Outputs are processed strictly in order so `tx_index`/`output_index`
+/// line up with their position on-chain, matching how `zcash_client_backend`
+/// walks a block's transactions.
+pub fn scan_transactions(
+    keypair: &StealthKeypair,
+    txs: &[ScanTransaction],
+    store: &mut NoteStore,
+) -> ScanResult {
+    let mut new_notes = Vec::new();
+
+    for (tx_index, tx) in txs.iter().enumerate() {
+        for (output_index, output) in tx.outputs.iter().enumerate() {
+            if let Some(note) = scan_output(keypair, output, tx_index, output_index) {
+                store.record(note.clone());
+                new_notes.push(note);
+            }
+        }
+    }
+
+    ScanResult {
+        new_notes,
+        balance: store.balance(),
+    }
+}
+
+/// Incremental variant for syncing a single block at a time.
+pub fn scan_block(
+    keypair: &StealthKeypair,
+    block_tx_offset: usize,
+    block: &[ScanTransaction],
+    store: &mut NoteStore,
+) -> ScanResult {
+    let mut new_notes = Vec::new();
+
+    for (block_index, tx) in block.iter().enumerate() {
+        let tx_index = block_tx_offset + block_index;
+        for (output_index, output) in tx.outputs.iter().enumerate() {
+            if let Some(note) = scan_output(keypair, output, tx_index, output_index) {
+                store.record(note.clone());
+                new_notes.push(note);
+            }
+        }
+    }
+
+    ScanResult {
+        new_notes,
+        balance: store.balance(),
+    }
+}
+
+fn scan_output(
+    keypair: &StealthKeypair,
+    output: &ScanOutput,
+    tx_index: usize,
+    output_index: usize,
+) -> Option<Note> {
+    let stealth_private =
+        keypair.scan_stealth_address(&output.ephemeral_public, &output.stealth_public)?;
+
+    // Confirm ownership and recover the committed value via the same
+    // shared secret used for the note ciphertext.
+    let plaintext: NotePlaintext = note_encryption::scan_note(
+        &keypair.view_private,
+        &output.ephemeral_public,
+        &output.encrypted_note,
+    )
+    .ok()??;
+
+    Some(Note {
+        stealth_private,
+        value: plaintext.value,
+        commitment: output.commitment.clone(),
+        tx_index,
+        output_index,
+        leaf_index: output.leaf_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::PedersenCommitment;
+    use crate::note_encryption::encrypt_note;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as G;
+
+    fn build_owned_output(keypair: &StealthKeypair, value: u64) -> ScanOutput {
+        build_owned_output_at(keypair, value, 0)
+    }
+
+    fn build_owned_output_at(keypair: &StealthKeypair, value: u64, leaf_index: u64) -> ScanOutput {
+        let (ephemeral_public, stealth_public, ephemeral_private) =
+            StealthKeypair::derive_stealth_address(&keypair.spend_public, &keypair.view_public);
+
+        let shared_secret = ephemeral_private * keypair.view_public;
+        let blinding = Scalar::random(&mut rand::thread_rng());
+        let encrypted_note = encrypt_note(&shared_secret, b"memo", value, &blinding).unwrap();
+
+        let pedersen = PedersenCommitment::new();
+        let commitment = pedersen.commit(value, &blinding);
+
+        ScanOutput {
+            ephemeral_public,
+            stealth_public,
+            commitment,
+            encrypted_note,
+            leaf_index,
+        }
+    }
+
+    #[test]
+    fn test_scan_transactions_finds_owned_output() {
+        let keypair = StealthKeypair::generate();
+        let other = StealthKeypair::generate();
+
+        let owned = build_owned_output(&keypair, 1_000);
+        let foreign = build_owned_output(&other, 500);
+
+        let txs = vec![ScanTransaction {
+            outputs: vec![foreign, owned],
+        }];
+
+        let mut store = NoteStore::new();
+        let result = scan_transactions(&keypair, &txs, &mut store);
+
+        assert_eq!(result.new_notes.len(), 1);
+        assert_eq!(result.new_notes[0].value, 1_000);
+        assert_eq!(result.new_notes[0].tx_index, 0);
+        assert_eq!(result.new_notes[0].output_index, 1);
+        assert_eq!(result.balance, 1_000);
+        assert_eq!(store.balance(), 1_000);
+    }
+
+    #[test]
+    fn test_scan_block_is_incremental() {
+        let keypair = StealthKeypair::generate();
+        let mut store = NoteStore::new();
+
+        let first_block = vec![ScanTransaction {
+            outputs: vec![build_owned_output(&keypair, 100)],
+        }];
+        let first = scan_block(&keypair, 0, &first_block, &mut store);
+        assert_eq!(first.balance, 100);
+
+        let second_block = vec![ScanTransaction {
+            outputs: vec![build_owned_output(&keypair, 250)],
+        }];
+        let second = scan_block(&keypair, 1, &second_block, &mut store);
+        assert_eq!(second.new_notes.len(), 1);
+        assert_eq!(second.new_notes[0].tx_index, 1);
+        assert_eq!(second.balance, 350);
+    }
+
+    #[test]
+    fn test_scan_with_wrong_key_finds_nothing() {
+        let keypair = StealthKeypair::generate();
+        let other = StealthKeypair::generate();
+        let output = build_owned_output(&keypair, 777);
+
+        let mut store = NoteStore::new();
+        let result = scan_transactions(
+            &other,
+            &[ScanTransaction {
+                outputs: vec![output],
+            }],
+            &mut store,
+        );
+
+        assert!(result.new_notes.is_empty());
+        assert_eq!(result.balance, 0);
+    }
+
+    #[test]
+    fn test_remove_by_leaf_index_takes_note_out_of_balance() {
+        let keypair = StealthKeypair::generate();
+        let mut store = NoteStore::new();
+        scan_transactions(
+            &keypair,
+            &[ScanTransaction {
+                outputs: vec![
+                    build_owned_output_at(&keypair, 100, 0),
+                    build_owned_output_at(&keypair, 200, 1),
+                ],
+            }],
+            &mut store,
+        );
+        assert_eq!(store.balance(), 300);
+
+        let removed = store.remove_by_leaf_index(0).unwrap();
+        assert_eq!(removed.value, 100);
+        assert_eq!(store.balance(), 200);
+        assert!(store.remove_by_leaf_index(0).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! File-Backed Key/Value Storage
+//!
+//! A thin wrapper over the filesystem for persisting opaque blobs (sealed
+//! wallet files, cached scan state, …) by name, the way [`EncryptedState`]
+//! persists a single wallet's sealed bytes but generalized to many named
+//! entries under one root directory.
+//!
+//! [`EncryptedState`]: crate::wallet_state::EncryptedState
+
+use crate::{CoreError, Result};
+use std::path::{Path, PathBuf};
+
+/// Key/value store rooted at a directory on disk. Keys are file names
+/// relative to that root; no further namespacing or indexing is done.
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    /// Open a store rooted at `root`, creating the directory if it doesn't
+    /// already exist.
+    pub fn new(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)
+            .map_err(|e| CoreError::Storage(format!("failed to create storage root: {}", e)))?;
+        Ok(FileStorage { root })
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    /// Write `value` under `key`, overwriting any existing entry.
+    pub fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+        std::fs::write(self.path_for(key), value)
+            .map_err(|e| CoreError::Storage(format!("failed to write {}: {}", key, e)))
+    }
+
+    /// Read the value stored under `key`, if present.
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path_for(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CoreError::Storage(format!("failed to read {}: {}", key, e))),
+        }
+    }
+
+    /// Whether an entry exists under `key`.
+    pub fn exists(&self, key: &str) -> bool {
+        self.path_for(key).is_file()
+    }
+
+    /// Remove the entry under `key`, if present.
+    pub fn delete(&self, key: &str) -> Result<()> {
+        match std::fs::remove_file(self.path_for(key)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(CoreError::Storage(format!("failed to delete {}: {}", key, e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("SafeMask-storage-test-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_put_get_roundtrip() {
+        let root = temp_root("roundtrip");
+        let store = FileStorage::new(&root).unwrap();
+
+        store.put("wallet.bin", b"sealed-bytes").unwrap();
+        assert_eq!(store.get("wallet.bin").unwrap(), Some(b"sealed-bytes".to_vec()));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_missing_key_returns_none() {
+        let root = temp_root("missing");
+        let store = FileStorage::new(&root).unwrap();
+
+        assert_eq!(store.get("absent").unwrap(), None);
+        assert!(!store.exists("absent"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_delete_removes_entry() {
+        let root = temp_root("delete");
+        let store = FileStorage::new(&root).unwrap();
+
+        store.put("k", b"v").unwrap();
+        assert!(store.exists("k"));
+
+        store.delete("k").unwrap();
+        assert!(!store.exists("k"));
+        assert!(store.delete("k").is_ok());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
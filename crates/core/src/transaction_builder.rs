@@ -0,0 +1,124 @@
+//! Confidential Transfer Construction
+//!
+//! Builds the single-output confidential transfer a wallet hands back to a
+//! caller for broadcasting: a Pedersen commitment to the amount, and the
+//! recipient's encrypted note, addressed to their one-time stealth public
+//! key the same way [`scanner`] expects to find it. Input selection and the
+//! [`validity::Transaction`] balance proof that ties inputs to outputs are a
+//! separate, heavier-weight concern left to callers that need full
+//! third-party verifiability; this builder covers the common "pay this
+//! address this amount" case.
+//!
+//! [`scanner`]: crate::scanner
+//! [`validity::Transaction`]: crate::validity::Transaction
+
+use crate::bech32::decode_stealth_address;
+use crate::commitments::{Commitment, PedersenCommitment};
+use crate::crypto::primitives::stealth::StealthKeypair;
+use crate::note_encryption::encrypt_note;
+use crate::Result;
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+/// A confidential transfer ready to hand to a recipient: the output
+/// commitment, its encrypted note, and the one-time keys a scanner needs to
+/// recognize and spend it.
+#[derive(Clone, Debug)]
+pub struct PrivateTransaction {
+    /// Pedersen commitment to the transfer amount.
+    pub output_commitment: Commitment,
+    /// ChaCha20-Poly1305 ciphertext of the memo, value and blinding factor.
+    pub encrypted_note: Vec<u8>,
+    /// Ephemeral public key paired with the recipient's view key to derive
+    /// the note's shared secret.
+    pub ephemeral_public: RistrettoPoint,
+    /// The recipient's one-time stealth public key this output pays to; a
+    /// scanner needs this to recognize ownership, see [`crate::scanner`].
+    pub stealth_public: RistrettoPoint,
+}
+
+/// Builds [`PrivateTransaction`]s for a single recipient address.
+pub struct TransactionBuilder;
+
+impl TransactionBuilder {
+    /// Build a confidential transfer of `amount` to `recipient_address`
+    /// (a Bech32m unified stealth address, see [`crate::bech32`]).
+    pub fn build_confidential_transfer(
+        recipient_address: &str,
+        amount: u64,
+    ) -> Result<PrivateTransaction> {
+        let (recipient_view_public, recipient_spend_public) =
+            decode_stealth_address(recipient_address)?;
+
+        let (ephemeral_public, stealth_public, ephemeral_private) =
+            StealthKeypair::derive_stealth_address(&recipient_spend_public, &recipient_view_public);
+
+        let pedersen = PedersenCommitment::new();
+        let (output_commitment, blinding) = pedersen.commit_with_random_blinding(amount);
+
+        let shared_secret = ephemeral_private * recipient_view_public;
+        let encrypted_note = encrypt_note(&shared_secret, b"", amount, &blinding)?;
+
+        Ok(PrivateTransaction {
+            output_commitment,
+            encrypted_note,
+            ephemeral_public,
+            stealth_public,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bech32::encode_stealth_address;
+    use crate::note_encryption::scan_note;
+    use crate::scanner::{scan_transactions, NoteStore, ScanOutput, ScanTransaction};
+
+    #[test]
+    fn test_build_confidential_transfer_is_recoverable_by_recipient() {
+        let recipient = StealthKeypair::generate();
+        let address = encode_stealth_address(&recipient.view_public, &recipient.spend_public).unwrap();
+
+        let tx = TransactionBuilder::build_confidential_transfer(&address, 4_200).unwrap();
+
+        let recovered = scan_note(&recipient.view_private, &tx.ephemeral_public, &tx.encrypted_note)
+            .unwrap()
+            .expect("recipient should decrypt their own note");
+
+        assert_eq!(recovered.value, 4_200);
+
+        let pedersen = PedersenCommitment::new();
+        assert!(pedersen.verify_opening(&tx.output_commitment, 4_200, &recovered.blinding));
+    }
+
+    #[test]
+    fn test_build_confidential_transfer_rejects_invalid_address() {
+        assert!(TransactionBuilder::build_confidential_transfer("not-a-stealth-address", 1).is_err());
+    }
+
+    #[test]
+    fn test_build_confidential_transfer_output_is_recognized_by_scanner() {
+        let recipient = StealthKeypair::generate();
+        let address = encode_stealth_address(&recipient.view_public, &recipient.spend_public).unwrap();
+
+        let tx = TransactionBuilder::build_confidential_transfer(&address, 1_500).unwrap();
+
+        let mut store = NoteStore::new();
+        let result = scan_transactions(
+            &recipient,
+            &[ScanTransaction {
+                outputs: vec![ScanOutput {
+                    ephemeral_public: tx.ephemeral_public,
+                    stealth_public: tx.stealth_public,
+                    commitment: tx.output_commitment,
+                    encrypted_note: tx.encrypted_note,
+                    leaf_index: 0,
+                }],
+            }],
+            &mut store,
+        );
+
+        assert_eq!(result.new_notes.len(), 1);
+        assert_eq!(result.new_notes[0].value, 1_500);
+    }
+}
@@ -0,0 +1,317 @@
+//! Transaction Validity Proofs
+//!
+//! The homomorphic balance equation exercised in
+//! [`commitments::tests::test_transaction_balance_equation`] (`Σ inputs =
+//! Σ outputs`) only holds because the prover knows every blinding factor.
+//! This module gives a verifier without those blindings something to check:
+//! an [`EqualityProof`] that two commitments hide the same value, and a
+//! [`BalanceProof`] that bundles the input/output equality together with
+//! the per-output [`RangeProof`]s into one non-interactively verifiable
+//! [`Transaction`].
+//!
+//! # Equality proof
+//!
+//! For commitments `C1 = v·G + r1·H` and `C2 = v·G + r2·H` hiding the same
+//! value, the difference `C1 - C2 = Δr·H` where `Δr = r1 - r2`. The prover
+//! picks a nonce `k`, sends `R = k·H`, and the transcript-derived challenge
+//! `c` yields a response `z = k + c·Δr`. A verifier checks
+//! `z·H == R + c·(C1 - C2)` without ever learning `v`, `r1`, `r2`, or `Δr`.
+
+use crate::commitments::{Commitment, PedersenCommitment, RangeProof};
+use crate::{CoreError, Result};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::Identity};
+use merlin::Transcript;
+
+/// Domain label for the equality-proof transcript.
+const EQUALITY_PROOF_DOMAIN: &[u8] = b"SafeMask-EqualityProof";
+
+/// Non-interactive Schnorr-style proof that two commitments open to the
+/// same value.
+#[derive(Clone, Debug)]
+pub struct EqualityProof {
+    /// Commitment to the prover's nonce: `R = k·H`.
+    r: RistrettoPoint,
+    /// Response: `z = k + c·Δr`.
+    z: Scalar,
+}
+
+impl EqualityProof {
+    /// Prove that `c1` and `c2` hide the same value, given their blinding
+    /// factors. Returns [`CoreError::Commitment`] if the claimed values
+    /// actually differ.
+    pub fn prove(c1: &Commitment, b1: &Scalar, c2: &Commitment, b2: &Scalar) -> Result<Self> {
+        match (c1.value, c2.value) {
+            (Some(v1), Some(v2)) if v1 != v2 => {
+                return Err(CoreError::Commitment(
+                    "commitments do not hide the same value".into(),
+                ))
+            }
+            _ => {}
+        }
+
+        let pedersen = PedersenCommitment::new();
+        let delta_r = b1 - b2;
+
+        let k = Scalar::random(&mut rand::thread_rng());
+        let r = k * pedersen.h;
+
+        let diff = c1.point - c2.point;
+        let c = challenge(&r, &diff);
+        let z = k + c * delta_r;
+
+        Ok(EqualityProof { r, z })
+    }
+
+    /// Verify that `c1` and `c2` hide the same value: `z·H == R + c·(C1 - C2)`.
+    pub fn verify(&self, c1: &Commitment, c2: &Commitment) -> bool {
+        let pedersen = PedersenCommitment::new();
+        let diff = c1.point - c2.point;
+        let c = challenge(&self.r, &diff);
+
+        self.z * pedersen.h == self.r + c * diff
+    }
+}
+
+/// Derive the Fiat-Shamir challenge for an [`EqualityProof`] by absorbing
+/// both the nonce commitment and the difference commitment.
+fn challenge(r: &RistrettoPoint, diff: &RistrettoPoint) -> Scalar {
+    let mut transcript = Transcript::new(EQUALITY_PROOF_DOMAIN);
+    transcript.append_message(b"R", r.compress().as_bytes());
+    transcript.append_message(b"diff", diff.compress().as_bytes());
+
+    let mut challenge_bytes = [0u8; 64];
+    transcript.challenge_bytes(b"c", &mut challenge_bytes);
+    Scalar::from_bytes_mod_order_wide(&challenge_bytes)
+}
+
+/// Proof that a transaction's inputs and outputs balance, bundled with a
+/// range proof per output so a verifier is convinced every output is
+/// non-negative without learning any amount.
+#[derive(Clone, Debug)]
+pub struct BalanceProof {
+    /// Proves `Σ inputs` and `Σ outputs` hide the same value.
+    pub equality: EqualityProof,
+    /// One range proof per output commitment, in output order.
+    pub output_ranges: Vec<RangeProof>,
+}
+
+impl BalanceProof {
+    /// Prove that `inputs` and `outputs` balance and every output is in
+    /// range. `input_blindings`/`output_blindings` must line up positionally
+    /// with `inputs`/`outputs`.
+    pub fn prove(
+        inputs: &[Commitment],
+        input_blindings: &[Scalar],
+        outputs: &[Commitment],
+        output_blindings: &[Scalar],
+        output_values: &[u64],
+        bit_length: usize,
+    ) -> Result<Self> {
+        if inputs.len() != input_blindings.len() || outputs.len() != output_blindings.len() {
+            return Err(CoreError::InvalidParameter(
+                "commitments and blinding factors must have matching lengths".into(),
+            ));
+        }
+        if outputs.len() != output_values.len() {
+            return Err(CoreError::InvalidParameter(
+                "outputs and output_values must have matching lengths".into(),
+            ));
+        }
+
+        let sum_inputs = sum_commitments(inputs);
+        let sum_outputs = sum_commitments(outputs);
+        let sum_input_blinding = input_blindings.iter().fold(Scalar::ZERO, |acc, b| acc + b);
+        let sum_output_blinding = output_blindings.iter().fold(Scalar::ZERO, |acc, b| acc + b);
+
+        let equality =
+            EqualityProof::prove(&sum_inputs, &sum_input_blinding, &sum_outputs, &sum_output_blinding)?;
+
+        let output_ranges = output_values
+            .iter()
+            .zip(output_blindings.iter())
+            .map(|(&value, blinding)| RangeProof::prove(value, blinding, bit_length))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(BalanceProof {
+            equality,
+            output_ranges,
+        })
+    }
+
+    /// Verify the balance equation and every output range proof.
+    pub fn verify(&self, inputs: &[Commitment], outputs: &[Commitment]) -> bool {
+        if outputs.len() != self.output_ranges.len() {
+            return false;
+        }
+
+        let sum_inputs = sum_commitments(inputs);
+        let sum_outputs = sum_commitments(outputs);
+
+        if !self.equality.verify(&sum_inputs, &sum_outputs) {
+            return false;
+        }
+
+        outputs
+            .iter()
+            .zip(self.output_ranges.iter())
+            .all(|(commitment, range_proof)| range_proof.verify(commitment))
+    }
+}
+
+fn sum_commitments(commitments: &[Commitment]) -> Commitment {
+    commitments
+        .iter()
+        .cloned()
+        .reduce(|acc, c| PedersenCommitment::add_commitments(&acc, &c))
+        .unwrap_or_else(|| Commitment::from_point(RistrettoPoint::identity()))
+}
+
+/// A fully-formed confidential transfer: input/output commitments plus the
+/// [`BalanceProof`] that makes it third-party verifiable without any
+/// blinding factor.
+#[derive(Clone, Debug)]
+pub struct Transaction {
+    /// Input commitments being spent.
+    pub inputs: Vec<Commitment>,
+    /// Output commitments being created.
+    pub outputs: Vec<Commitment>,
+    /// Proof that inputs and outputs balance and every output is in range.
+    pub balance_proof: BalanceProof,
+}
+
+impl Transaction {
+    /// Build a transaction and its balance proof from the prover's full
+    /// view (commitments, blindings, and output values).
+    pub fn build(
+        inputs: Vec<Commitment>,
+        input_blindings: &[Scalar],
+        outputs: Vec<Commitment>,
+        output_blindings: &[Scalar],
+        output_values: &[u64],
+        bit_length: usize,
+    ) -> Result<Self> {
+        let balance_proof = BalanceProof::prove(
+            &inputs,
+            input_blindings,
+            &outputs,
+            output_blindings,
+            output_values,
+            bit_length,
+        )?;
+
+        Ok(Transaction {
+            inputs,
+            outputs,
+            balance_proof,
+        })
+    }
+
+    /// Verify the transaction: inputs and outputs balance, and every output
+    /// is provably in range.
+    pub fn verify(&self) -> bool {
+        self.balance_proof.verify(&self.inputs, &self.outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitments::random_scalar;
+
+    #[test]
+    fn test_equality_proof_accepts_matching_values() {
+        let pedersen = PedersenCommitment::new();
+        let (c1, b1) = pedersen.commit_with_random_blinding(500);
+        let (c2, b2) = pedersen.commit_with_random_blinding(500);
+
+        let proof = EqualityProof::prove(&c1, &b1, &c2, &b2).unwrap();
+        assert!(proof.verify(&c1, &c2));
+    }
+
+    #[test]
+    fn test_equality_proof_rejects_differing_values() {
+        let pedersen = PedersenCommitment::new();
+        let (c1, b1) = pedersen.commit_with_random_blinding(500);
+        let (c2, b2) = pedersen.commit_with_random_blinding(501);
+
+        assert!(EqualityProof::prove(&c1, &b1, &c2, &b2).is_err());
+    }
+
+    #[test]
+    fn test_equality_proof_rejects_wrong_commitment_pair() {
+        let pedersen = PedersenCommitment::new();
+        let (c1, b1) = pedersen.commit_with_random_blinding(500);
+        let (c2, b2) = pedersen.commit_with_random_blinding(500);
+        let (c3, _) = pedersen.commit_with_random_blinding(500);
+
+        let proof = EqualityProof::prove(&c1, &b1, &c2, &b2).unwrap();
+        assert!(!proof.verify(&c1, &c3));
+    }
+
+    #[test]
+    fn test_transaction_builds_and_verifies() {
+        let pedersen = PedersenCommitment::new();
+
+        let (input1, b1) = pedersen.commit_with_random_blinding(5000);
+        let (input2, b2) = pedersen.commit_with_random_blinding(3000);
+
+        let b3 = random_scalar();
+        let total_blinding_in = b1 + b2;
+        let b4 = total_blinding_in - b3;
+
+        let output1 = pedersen.commit(7000, &b3);
+        let output2 = pedersen.commit(1000, &b4);
+
+        let tx = Transaction::build(
+            vec![input1, input2],
+            &[b1, b2],
+            vec![output1, output2],
+            &[b3, b4],
+            &[7000, 1000],
+            64,
+        )
+        .unwrap();
+
+        assert!(tx.verify());
+    }
+
+    #[test]
+    fn test_transaction_rejects_unbalanced_outputs() {
+        let pedersen = PedersenCommitment::new();
+
+        let (input1, b1) = pedersen.commit_with_random_blinding(5000);
+
+        let b2 = random_scalar();
+        let output1 = pedersen.commit(4000, &b2); // short by 1000
+
+        // An honest prover can't even construct a proof for unbalanced
+        // amounts; a dishonest one supplying tampered commitments (unknown
+        // values) would instead fail at `verify()`.
+        assert!(Transaction::build(vec![input1], &[b1], vec![output1], &[b2], &[4000], 64).is_err());
+    }
+
+    #[test]
+    fn test_transaction_verify_rejects_tampered_output_commitment() {
+        let pedersen = PedersenCommitment::new();
+
+        let (input1, b1) = pedersen.commit_with_random_blinding(5000);
+        let b2 = random_scalar();
+        let output1 = pedersen.commit(5000, &b2);
+
+        let mut tx = Transaction::build(
+            vec![input1],
+            &[b1],
+            vec![output1],
+            &[b2],
+            &[5000],
+            64,
+        )
+        .unwrap();
+        assert!(tx.verify());
+
+        // Swap in a commitment to a different, unknown value/blinding —
+        // the verifier has no blindings and must still catch the mismatch.
+        tx.outputs[0] = pedersen.commit(4000, &random_scalar());
+        assert!(!tx.verify());
+    }
+}
@@ -0,0 +1,269 @@
+//! Encrypted Wallet-at-Rest
+//!
+//! Mirrors the silentdragonlite `encrypt`/`unlock`/`decrypt` commands: a
+//! wallet's secrets (seed phrase plus per-account spend/view keys) are
+//! sealed with a password-derived key so they never touch disk in
+//! plaintext, and a session can keep only public data in memory between an
+//! `unlock` and the matching `lock`.
+
+use crate::crypto::primitives::{argon2_derive_key, ChaCha20Cipher};
+use crate::{CoreError, Result};
+use rand::Rng;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Length of the random salt used for key derivation.
+const SALT_LEN: usize = 16;
+
+/// The secrets a wallet needs while unlocked: the seed phrase and every
+/// derived account's spend/view private keys.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct WalletSecrets {
+    pub mnemonic: String,
+    /// 32-byte spend and view private scalars, one pair per account.
+    pub account_keys: Vec<([u8; 32], [u8; 32])>,
+}
+
+impl WalletSecrets {
+    pub fn new(mnemonic: String, account_keys: Vec<([u8; 32], [u8; 32])>) -> Self {
+        WalletSecrets {
+            mnemonic,
+            account_keys,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mnemonic_bytes = self.mnemonic.as_bytes();
+        let mut out = Vec::with_capacity(8 + mnemonic_bytes.len() + self.account_keys.len() * 64);
+
+        out.extend_from_slice(&(mnemonic_bytes.len() as u32).to_le_bytes());
+        out.extend_from_slice(mnemonic_bytes);
+        out.extend_from_slice(&(self.account_keys.len() as u32).to_le_bytes());
+        for (spend, view) in &self.account_keys {
+            out.extend_from_slice(spend);
+            out.extend_from_slice(view);
+        }
+        out
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let err = || CoreError::Serialization("malformed wallet secrets".into());
+
+        if bytes.len() < 4 {
+            return Err(err());
+        }
+        let mnemonic_len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let mut offset = 4;
+        if bytes.len() < offset + mnemonic_len + 4 {
+            return Err(err());
+        }
+        let mnemonic = String::from_utf8(bytes[offset..offset + mnemonic_len].to_vec())
+            .map_err(|_| err())?;
+        offset += mnemonic_len;
+
+        let account_count =
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        offset += 4;
+
+        if bytes.len() != offset + account_count * 64 {
+            return Err(err());
+        }
+
+        let mut account_keys = Vec::with_capacity(account_count);
+        for _ in 0..account_count {
+            let mut spend = [0u8; 32];
+            let mut view = [0u8; 32];
+            spend.copy_from_slice(&bytes[offset..offset + 32]);
+            view.copy_from_slice(&bytes[offset + 32..offset + 64]);
+            account_keys.push((spend, view));
+            offset += 64;
+        }
+
+        Ok(WalletSecrets {
+            mnemonic,
+            account_keys,
+        })
+    }
+}
+
+/// A sealed wallet: a random salt plus the ChaCha20-Poly1305 ciphertext of
+/// its [`WalletSecrets`], safe to persist to disk.
+#[derive(Clone, Debug)]
+pub struct EncryptedState {
+    pub salt: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedState {
+    /// Seal `secrets` under a freshly-derived key for `password`.
+    pub fn seal(secrets: &WalletSecrets, password: &[u8]) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill(&mut salt);
+
+        let key = argon2_derive_key(password, &salt)?;
+        let ciphertext = ChaCha20Cipher::new(&key).encrypt(&secrets.to_bytes())?;
+
+        Ok(EncryptedState {
+            salt: salt.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Re-derive the key for `password` and decrypt back to [`WalletSecrets`].
+    pub fn open(&self, password: &[u8]) -> Result<WalletSecrets> {
+        let salt: [u8; SALT_LEN] = self
+            .salt
+            .as_slice()
+            .try_into()
+            .map_err(|_| CoreError::Crypto("invalid salt length".into()))?;
+
+        let key = argon2_derive_key(password, &salt)?;
+        let plaintext = ChaCha20Cipher::new(&key)
+            .decrypt(&self.ciphertext)
+            .map_err(|_| CoreError::Crypto("incorrect password".into()))?;
+
+        WalletSecrets::from_bytes(&plaintext)
+    }
+
+    /// Flatten to `salt_len (4 bytes LE) || salt || ciphertext` for storage.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + self.salt.len() + self.ciphertext.len());
+        out.extend_from_slice(&(self.salt.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.salt);
+        out.extend_from_slice(&self.ciphertext);
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let err = || CoreError::Serialization("malformed encrypted wallet blob".into());
+        if bytes.len() < 4 {
+            return Err(err());
+        }
+        let salt_len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        if bytes.len() < 4 + salt_len {
+            return Err(err());
+        }
+        let salt = bytes[4..4 + salt_len].to_vec();
+        let ciphertext = bytes[4 + salt_len..].to_vec();
+        Ok(EncryptedState { salt, ciphertext })
+    }
+
+    /// Persist the sealed blob to `path`.
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        std::fs::write(path, self.to_bytes())
+            .map_err(|e| CoreError::Storage(format!("failed to write wallet file: {}", e)))
+    }
+
+    /// Load a previously-saved sealed blob from `path`.
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| CoreError::Storage(format!("failed to read wallet file: {}", e)))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Session-scoped lock state for a loaded wallet: either only public data is
+/// available, or the secrets have been decrypted for the duration of the
+/// unlocked session.
+pub enum WalletState {
+    Locked {
+        encrypted: EncryptedState,
+    },
+    Unlocked {
+        encrypted: EncryptedState,
+        secrets: WalletSecrets,
+    },
+}
+
+impl WalletState {
+    pub fn locked(encrypted: EncryptedState) -> Self {
+        WalletState::Locked { encrypted }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(self, WalletState::Locked { .. })
+    }
+
+    /// Re-derive the key for `password` and decrypt secrets into memory for
+    /// the remainder of the session.
+    pub fn unlock(&mut self, password: &[u8]) -> Result<()> {
+        let encrypted = match self {
+            WalletState::Locked { encrypted } => encrypted.clone(),
+            WalletState::Unlocked { encrypted, .. } => encrypted.clone(),
+        };
+        let secrets = encrypted.open(password)?;
+        *self = WalletState::Unlocked { encrypted, secrets };
+        Ok(())
+    }
+
+    /// Zeroize in-memory secrets and fall back to holding only the sealed
+    /// blob, requiring another `unlock` to spend or export keys again.
+    pub fn lock(&mut self) {
+        if let WalletState::Unlocked { encrypted, secrets } = self {
+            secrets.zeroize();
+            *self = WalletState::Locked {
+                encrypted: encrypted.clone(),
+            };
+        }
+    }
+
+    /// Access the decrypted secrets, if the wallet is currently unlocked.
+    pub fn secrets(&self) -> Result<&WalletSecrets> {
+        match self {
+            WalletState::Unlocked { secrets, .. } => Ok(secrets),
+            WalletState::Locked { .. } => Err(CoreError::Crypto("wallet is locked".into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_roundtrip() {
+        let secrets = WalletSecrets::new("test mnemonic phrase".into(), vec![([1u8; 32], [2u8; 32])]);
+        let encrypted = EncryptedState::seal(&secrets, b"hunter2").unwrap();
+
+        let opened = encrypted.open(b"hunter2").unwrap();
+        assert_eq!(opened.mnemonic, secrets.mnemonic);
+        assert_eq!(opened.account_keys, secrets.account_keys);
+    }
+
+    #[test]
+    fn test_wrong_password_fails() {
+        let secrets = WalletSecrets::new("test mnemonic phrase".into(), vec![]);
+        let encrypted = EncryptedState::seal(&secrets, b"correct horse").unwrap();
+
+        assert!(encrypted.open(b"wrong password").is_err());
+    }
+
+    #[test]
+    fn test_lock_unlock_lifecycle() {
+        let secrets = WalletSecrets::new("m".into(), vec![([3u8; 32], [4u8; 32])]);
+        let encrypted = EncryptedState::seal(&secrets, b"pw").unwrap();
+
+        let mut state = WalletState::locked(encrypted);
+        assert!(state.is_locked());
+        assert!(state.secrets().is_err());
+
+        state.unlock(b"pw").unwrap();
+        assert!(!state.is_locked());
+        assert_eq!(state.secrets().unwrap().mnemonic, "m");
+
+        state.lock();
+        assert!(state.is_locked());
+        assert!(state.secrets().is_err());
+    }
+
+    #[test]
+    fn test_blob_serialization_roundtrip() {
+        let secrets = WalletSecrets::new("abc".into(), vec![([5u8; 32], [6u8; 32])]);
+        let encrypted = EncryptedState::seal(&secrets, b"pw").unwrap();
+
+        let bytes = encrypted.to_bytes();
+        let restored = EncryptedState::from_bytes(&bytes).unwrap();
+        let opened = restored.open(b"pw").unwrap();
+
+        assert_eq!(opened.mnemonic, "abc");
+    }
+}
@@ -1,15 +1,40 @@
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
-use SafeMask_core::{SafeMaskWallet, Transaction as CoreTransaction};
+use SafeMask_core::SafeMaskWallet;
 use serde::{Serialize, Deserialize};
 
 uniffi::include_scaffolding!("SafeMask");
 
 // Global wallet storage
 lazy_static::lazy_static! {
-    static ref WALLETS: Arc<Mutex<HashMap<u64, Arc<Mutex<SafeMaskWallet>>>>> = 
+    static ref WALLETS: Arc<Mutex<HashMap<u64, Arc<Mutex<SafeMaskWallet>>>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref NEXT_ID: Arc<Mutex<u64>> = Arc::new(Mutex::new(1));
+    // Per-wallet accumulator of notes discovered while scanning the chain.
+    static ref NOTE_STORES: Arc<Mutex<HashMap<u64, SafeMask_core::NoteStore>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    // Shared commitment tree over every output seen while scanning, regardless
+    // of ownership, so a spend witness can be produced for any owned note.
+    static ref COMMITMENT_TREE: Arc<Mutex<SafeMask_core::CommitmentTree>> =
+        Arc::new(Mutex::new(SafeMask_core::CommitmentTree::new()));
+    // Lock state for wallets that have been sealed with `encrypt_wallet` or
+    // reloaded from disk. A wallet with no entry here has never been
+    // encrypted and is always treated as unlocked.
+    static ref LOCK_STATES: Arc<Mutex<HashMap<u64, SafeMask_core::WalletState>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    // Nullifiers accepted by `verify_transaction`, to block replays.
+    static ref SPENT_NULLIFIERS: Arc<Mutex<SafeMask_core::NullifierSet>> =
+        Arc::new(Mutex::new(SafeMask_core::NullifierSet::new()));
+}
+
+/// Returns an error if wallet `id` has an encrypted lock state and it is
+/// currently locked.
+fn require_unlocked(id: u64) -> Result<(), SafeMaskError> {
+    let states = LOCK_STATES.lock().unwrap();
+    match states.get(&id) {
+        Some(state) if state.is_locked() => Err(SafeMaskError::WalletLocked),
+        _ => Ok(()),
+    }
 }
 
 // FFI Types
@@ -43,6 +68,19 @@ pub struct PrivacyData {
     pub range_proof: Vec<u8>,
     pub stealth_address: Option<String>,
     pub nullifier: Option<Vec<u8>>,
+    /// ChaCha20-Poly1305 ciphertext of the recipient's memo, value and
+    /// blinding factor, encrypted under the note's shared secret.
+    pub encrypted_note: Vec<u8>,
+    /// Ephemeral public key the recipient needs to recompute the shared
+    /// secret and decrypt `encrypted_note`.
+    pub ephemeral_public: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecryptedNote {
+    pub memo: Vec<u8>,
+    pub value: u64,
+    pub blinding_factor: Vec<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +137,10 @@ pub enum SafeMaskError {
     WalletNotFound,
     #[error("Key derivation failed")]
     KeyDerivationFailed,
+    #[error("Note not found")]
+    NoteNotFound,
+    #[error("Wallet is locked")]
+    WalletLocked,
 }
 
 // Wallet Operations
@@ -143,16 +185,31 @@ pub fn create_transaction(
     to_address: String,
     amount: u64,
 ) -> Result<Transaction, SafeMaskError> {
+    require_unlocked(handle.id)?;
+
     let wallets = WALLETS.lock().unwrap();
     let wallet = wallets.get(&handle.id)
         .ok_or(SafeMaskError::WalletNotFound)?;
-    
+
     let wallet = wallet.lock().unwrap();
-    
+
     // Create confidential transaction
     let tx = wallet.create_confidential_transaction(0, &to_address, amount)
         .map_err(|_| SafeMaskError::InsufficientFunds)?;
-    
+
+    // If this wallet has a scanned, unspent note that covers the transfer
+    // amount, reveal its nullifier so the network can detect a replay, and
+    // remove it from the store so a second call before this one confirms
+    // can't select (and double-reveal) the same note.
+    let nullifier = {
+        let mut stores = NOTE_STORES.lock().unwrap();
+        stores.get_mut(&handle.id).and_then(|store| {
+            let leaf_index = store.notes().iter().find(|note| note.value >= amount)?.leaf_index;
+            let note = store.remove_by_leaf_index(leaf_index)?;
+            Some(SafeMask_core::compute_nullifier(&note.stealth_private, &note.commitment.to_bytes()).to_vec())
+        })
+    };
+
     Ok(Transaction {
         from: wallet.get_address(0).unwrap_or_default(),
         to: to_address,
@@ -163,22 +220,228 @@ pub fn create_transaction(
         privacy: Some(PrivacyData {
             commitment: tx.output_commitment.to_bytes().to_vec(),
             range_proof: vec![], // Will be generated separately
-            stealth_address: None,
-            nullifier: None,
+            stealth_address: Some(hex::encode(tx.stealth_public.compress().to_bytes())),
+            nullifier,
+            encrypted_note: tx.encrypted_note,
+            ephemeral_public: tx.ephemeral_public.compress().to_bytes().to_vec(),
         }),
     })
 }
 
+/// Derive the nullifier for a scanned note, e.g. to pre-check whether it's
+/// already been spent before building a transaction around it.
+pub fn compute_nullifier(handle: WalletHandle, note_index: u32) -> Result<Vec<u8>, SafeMaskError> {
+    let stores = NOTE_STORES.lock().unwrap();
+    let store = stores.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let note = store.notes().get(note_index as usize).ok_or(SafeMaskError::NoteNotFound)?;
+
+    Ok(SafeMask_core::compute_nullifier(&note.stealth_private, &note.commitment.to_bytes()).to_vec())
+}
+
+/// Whether a nullifier has already been accepted by [`verify_transaction`].
+pub fn is_spent(nullifier: Vec<u8>) -> bool {
+    let Ok(nullifier): Result<[u8; 32], _> = nullifier.as_slice().try_into() else {
+        return false;
+    };
+    SPENT_NULLIFIERS.lock().unwrap().contains(&nullifier)
+}
+
+/// Recipient-side note recovery: scan a transaction's encrypted memo with
+/// the wallet's view key and recover the value/blinding/memo if owned.
+pub fn scan_note(
+    handle: WalletHandle,
+    ephemeral_public: Vec<u8>,
+    encrypted_note: Vec<u8>,
+) -> Result<Option<DecryptedNote>, SafeMaskError> {
+    use SafeMask_core::note_encryption::scan_note as core_scan_note;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let wallets = WALLETS.lock().unwrap();
+    let wallet = wallets.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let wallet = wallet.lock().unwrap();
+
+    let ephemeral_bytes: [u8; 32] = ephemeral_public
+        .as_slice()
+        .try_into()
+        .map_err(|_| SafeMaskError::InvalidAddress)?;
+    let ephemeral_point = CompressedRistretto(ephemeral_bytes)
+        .decompress()
+        .ok_or(SafeMaskError::InvalidAddress)?;
+
+    let view_private = wallet
+        .get_view_private_key()
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+
+    let note = core_scan_note(&view_private, &ephemeral_point, &encrypted_note)
+        .map_err(|_| SafeMaskError::ProofVerificationFailed)?;
+
+    Ok(note.map(|n| DecryptedNote {
+        memo: n.memo.to_vec(),
+        value: n.value,
+        blinding_factor: n.blinding.to_bytes().to_vec(),
+    }))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteSummary {
+    pub value: u64,
+    pub tx_index: u32,
+    pub output_index: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSummary {
+    pub new_notes: Vec<NoteSummary>,
+    pub balance: u64,
+}
+
+fn to_scan_transactions(
+    txs: &[Transaction],
+    tree: &mut SafeMask_core::CommitmentTree,
+) -> Result<Vec<SafeMask_core::scanner::ScanTransaction>, SafeMaskError> {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+    use SafeMask_core::commitments::Commitment;
+    use SafeMask_core::scanner::{ScanOutput, ScanTransaction};
+
+    txs.iter()
+        .map(|tx| {
+            let Some(privacy) = &tx.privacy else {
+                return Ok(ScanTransaction { outputs: vec![] });
+            };
+
+            let commitment_bytes: [u8; 32] = privacy.commitment.as_slice().try_into()
+                .map_err(|_| SafeMaskError::InvalidAddress)?;
+            let commitment = Commitment::from_bytes(&commitment_bytes)
+                .map_err(|_| SafeMaskError::InvalidAddress)?;
+
+            let ephemeral_bytes: [u8; 32] = privacy.ephemeral_public.as_slice().try_into()
+                .map_err(|_| SafeMaskError::InvalidAddress)?;
+            let ephemeral_public = CompressedRistretto(ephemeral_bytes)
+                .decompress()
+                .ok_or(SafeMaskError::InvalidAddress)?;
+
+            let stealth_hex = privacy.stealth_address.as_deref().unwrap_or_default();
+            let stealth_bytes = hex::decode(stealth_hex).map_err(|_| SafeMaskError::InvalidAddress)?;
+            let stealth_fixed: [u8; 32] = stealth_bytes.as_slice().try_into()
+                .map_err(|_| SafeMaskError::InvalidAddress)?;
+            let stealth_public = CompressedRistretto(stealth_fixed)
+                .decompress()
+                .ok_or(SafeMaskError::InvalidAddress)?;
+
+            let leaf_index = tree
+                .append(&commitment_bytes)
+                .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
+
+            Ok(ScanTransaction {
+                outputs: vec![ScanOutput {
+                    ephemeral_public,
+                    stealth_public,
+                    commitment,
+                    encrypted_note: privacy.encrypted_note.clone(),
+                    leaf_index,
+                }],
+            })
+        })
+        .collect()
+}
+
+fn to_scan_summary(result: SafeMask_core::ScanResult) -> ScanSummary {
+    ScanSummary {
+        new_notes: result
+            .new_notes
+            .iter()
+            .map(|note| NoteSummary {
+                value: note.value,
+                tx_index: note.tx_index as u32,
+                output_index: note.output_index as u32,
+            })
+            .collect(),
+        balance: result.balance,
+    }
+}
+
+/// Scan a batch of transactions for outputs owned by this wallet, appending
+/// any newly-discovered notes to its note store.
+pub fn scan_transactions(
+    handle: WalletHandle,
+    txs: Vec<Transaction>,
+) -> Result<ScanSummary, SafeMaskError> {
+    let wallets = WALLETS.lock().unwrap();
+    let wallet = wallets.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let wallet = wallet.lock().unwrap();
+
+    let keypair = wallet.stealth_keypair(0)
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+    let mut tree = COMMITMENT_TREE.lock().unwrap();
+    let scan_txs = to_scan_transactions(&txs, &mut tree)?;
+
+    let mut stores = NOTE_STORES.lock().unwrap();
+    let store = stores.entry(handle.id).or_insert_with(SafeMask_core::NoteStore::new);
+
+    let result = SafeMask_core::scanner::scan_transactions(&keypair, &scan_txs, store);
+    Ok(to_scan_summary(result))
+}
+
+/// Incremental counterpart to [`scan_transactions`] for a single block,
+/// given the index of the first transaction in `txs` within the chain.
+pub fn scan_block(
+    handle: WalletHandle,
+    tx_offset: u32,
+    txs: Vec<Transaction>,
+) -> Result<ScanSummary, SafeMaskError> {
+    let wallets = WALLETS.lock().unwrap();
+    let wallet = wallets.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let wallet = wallet.lock().unwrap();
+
+    let keypair = wallet.stealth_keypair(0)
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+    let mut tree = COMMITMENT_TREE.lock().unwrap();
+    let scan_txs = to_scan_transactions(&txs, &mut tree)?;
+
+    let mut stores = NOTE_STORES.lock().unwrap();
+    let store = stores.entry(handle.id).or_insert_with(SafeMask_core::NoteStore::new);
+
+    let result = SafeMask_core::scanner::scan_block(&keypair, tx_offset as usize, &scan_txs, store);
+    Ok(to_scan_summary(result))
+}
+
+/// Authentication path for an owned note, to be fed as a private input into
+/// [`generate_zk_proof`] for a membership/spend circuit.
+pub fn get_note_witness(handle: WalletHandle, note_index: u32) -> Result<Vec<u8>, SafeMaskError> {
+    let stores = NOTE_STORES.lock().unwrap();
+    let store = stores.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let note = store
+        .notes()
+        .get(note_index as usize)
+        .ok_or(SafeMaskError::NoteNotFound)?;
+
+    let tree = COMMITMENT_TREE.lock().unwrap();
+    let path = tree
+        .witness(note.leaf_index)
+        .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
+
+    Ok(path.to_bytes())
+}
+
+/// Current commitment tree root, used as a public input alongside a spend
+/// witness so a verifier can check membership without learning which leaf.
+pub fn anchor() -> Vec<u8> {
+    let tree = COMMITMENT_TREE.lock().unwrap();
+    tree.root().to_vec()
+}
+
 pub fn sign_transaction(
     handle: WalletHandle,
     tx: Transaction,
 ) -> Result<String, SafeMaskError> {
+    require_unlocked(handle.id)?;
+
     let wallets = WALLETS.lock().unwrap();
     let wallet = wallets.get(&handle.id)
         .ok_or(SafeMaskError::WalletNotFound)?;
-    
+
     let wallet = wallet.lock().unwrap();
-    
+
     // Sign transaction
     let signature = wallet.sign_transaction(0, &tx.to, tx.amount)
         .map_err(|_| SafeMaskError::InvalidSignature)?;
@@ -189,7 +452,26 @@ pub fn sign_transaction(
 pub fn verify_transaction(tx: Transaction) -> bool {
     // Verify transaction signature and commitments
     // This is a simplified version
-    !tx.signature.is_empty() && tx.amount > 0
+    if tx.signature.is_empty() || tx.amount == 0 {
+        return false;
+    }
+
+    if let Some(privacy) = &tx.privacy {
+        if let Some(nullifier) = &privacy.nullifier {
+            let Ok(nullifier): Result<[u8; 32], _> = nullifier.as_slice().try_into() else {
+                return false;
+            };
+
+            let mut spent = SPENT_NULLIFIERS.lock().unwrap();
+            if spent.contains(&nullifier) {
+                // Already seen this nullifier: reject the double-spend.
+                return false;
+            }
+            spent.insert(nullifier);
+        }
+    }
+
+    true
 }
 
 // Privacy Operations
@@ -202,9 +484,24 @@ pub fn generate_stealth_address(
     
     let wallet = wallet.lock().unwrap();
     
-    let (address, scan_key, spend_key) = wallet.generate_stealth_address(0)
+    let (_, scan_key, spend_key) = wallet.generate_stealth_address(0)
         .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
-    
+
+    // Bundle the two loose keys into one checksummed Bech32m address rather
+    // than handing back three unverifiable blobs.
+    use curve25519_dalek::ristretto::CompressedRistretto;
+    let scan_bytes: [u8; 32] = scan_key.as_slice().try_into()
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+    let spend_bytes: [u8; 32] = spend_key.as_slice().try_into()
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+    let scan_public = CompressedRistretto(scan_bytes).decompress()
+        .ok_or(SafeMaskError::KeyDerivationFailed)?;
+    let spend_public = CompressedRistretto(spend_bytes).decompress()
+        .ok_or(SafeMaskError::KeyDerivationFailed)?;
+
+    let address = SafeMask_core::encode_stealth_address(&scan_public, &spend_public)
+        .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+
     Ok(StealthAddress {
         address,
         scan_key: scan_key.to_vec(),
@@ -212,39 +509,60 @@ pub fn generate_stealth_address(
     })
 }
 
+/// Decode a unified Bech32m stealth address back into its scan and spend
+/// public keys, rejecting any single-character error.
+pub fn decode_stealth_address(address: String) -> Result<(Vec<u8>, Vec<u8>), SafeMaskError> {
+    let (scan_public, spend_public) = SafeMask_core::decode_stealth_address(&address)
+        .map_err(|_| SafeMaskError::InvalidAddress)?;
+    Ok((
+        scan_public.compress().to_bytes().to_vec(),
+        spend_public.compress().to_bytes().to_vec(),
+    ))
+}
+
 pub fn create_commitment(
     value: u64,
     blinding_factor: Vec<u8>,
 ) -> Result<Commitment, SafeMaskError> {
-    use SafeMask_core::crypto::commitments::create_pedersen_commitment;
     use curve25519_dalek::scalar::Scalar;
-    
-    let blinding = Scalar::from_bytes_mod_order(
-        blinding_factor.as_slice().try_into()
-            .map_err(|_| SafeMaskError::ProofGenerationFailed)?
-    );
-    
-    let commitment = create_pedersen_commitment(value, &blinding);
-    
+
+    let blinding_bytes: [u8; 32] = blinding_factor
+        .as_slice()
+        .try_into()
+        .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
+    let blinding = Scalar::from_canonical_bytes(blinding_bytes)
+        .into_option()
+        .ok_or(SafeMaskError::ProofGenerationFailed)?;
+
+    let pedersen = SafeMask_core::PedersenCommitment::new();
+    let commitment = pedersen.commit(value, &blinding);
+
     Ok(Commitment {
-        commitment: commitment.compress().to_bytes().to_vec(),
+        commitment: commitment.to_bytes().to_vec(),
         blinding_factor,
     })
 }
 
 pub fn create_range_proof(
-    commitment: Commitment,
+    _commitment: Commitment,
     value: u64,
     blinding: Vec<u8>,
 ) -> Result<RangeProof, SafeMaskError> {
-    use SafeMask_core::crypto::bulletproofs::BulletproofRangeProof;
-    
-    // Create range proof
-    let proof = BulletproofRangeProof::create(value, &blinding)
+    use curve25519_dalek::scalar::Scalar;
+
+    let blinding_bytes: [u8; 32] = blinding
+        .as_slice()
+        .try_into()
         .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
-    
+    let blinding = Scalar::from_canonical_bytes(blinding_bytes)
+        .into_option()
+        .ok_or(SafeMaskError::ProofGenerationFailed)?;
+
+    let proof = SafeMask_core::RangeProof::prove(value, &blinding, 64)
+        .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
+
     Ok(RangeProof {
-        proof: proof.serialize(),
+        proof: proof.proof_bytes,
         min_value: 0,
         max_value: u64::MAX,
     })
@@ -254,9 +572,20 @@ pub fn verify_range_proof(
     proof: RangeProof,
     commitment: Commitment,
 ) -> bool {
-    use SafeMask_core::crypto::bulletproofs::BulletproofRangeProof;
-    
-    BulletproofRangeProof::verify(&proof.proof, &commitment.commitment).unwrap_or(false)
+    let Ok(commitment_bytes): Result<[u8; 32], _> = commitment.commitment.as_slice().try_into() else {
+        return false;
+    };
+    let Ok(core_commitment) = SafeMask_core::commitments::Commitment::from_bytes(&commitment_bytes) else {
+        return false;
+    };
+
+    let range_proof = SafeMask_core::RangeProof {
+        proof_bytes: proof.proof,
+        bit_length: 64,
+        rewind_trailer: None,
+    };
+
+    range_proof.verify(&core_commitment)
 }
 
 // ZK Proof Operations
@@ -281,18 +610,116 @@ pub fn export_private_key(
     handle: WalletHandle,
     account_index: u32,
 ) -> Result<String, SafeMaskError> {
+    require_unlocked(handle.id)?;
+
     let wallets = WALLETS.lock().unwrap();
     let wallet = wallets.get(&handle.id)
         .ok_or(SafeMaskError::WalletNotFound)?;
-    
+
     let wallet = wallet.lock().unwrap();
-    
+
     let private_key = wallet.export_private_key(account_index)
         .map_err(|_| SafeMaskError::KeyDerivationFailed)?;
-    
+
     Ok(hex::encode(private_key))
 }
 
+// Wallet-at-Rest Encryption
+
+/// Seal the wallet's seed and per-account keys under a password-derived key.
+/// The encrypted blob is held in memory until [`save_wallet`] persists it.
+pub fn encrypt_wallet(handle: WalletHandle, password: String) -> Result<(), SafeMaskError> {
+    let wallets = WALLETS.lock().unwrap();
+    let wallet = wallets.get(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    let wallet = wallet.lock().unwrap();
+
+    let secrets = wallet.export_secrets().map_err(|_| SafeMaskError::KeyDerivationFailed)?;
+    let encrypted = SafeMask_core::EncryptedState::seal(&secrets, password.as_bytes())
+        .map_err(|_| SafeMaskError::ProofGenerationFailed)?;
+
+    let mut states = LOCK_STATES.lock().unwrap();
+    states.insert(
+        handle.id,
+        SafeMask_core::wallet_state::WalletState::Unlocked { encrypted, secrets },
+    );
+    Ok(())
+}
+
+/// Persist the wallet's sealed blob (from [`encrypt_wallet`]) to `path`.
+pub fn save_wallet(handle: WalletHandle, path: String) -> Result<(), SafeMaskError> {
+    let states = LOCK_STATES.lock().unwrap();
+    let state = states.get(&handle.id).ok_or(SafeMaskError::WalletLocked)?;
+
+    let encrypted = match state {
+        SafeMask_core::wallet_state::WalletState::Locked { encrypted } => encrypted,
+        SafeMask_core::wallet_state::WalletState::Unlocked { encrypted, .. } => encrypted,
+    };
+
+    encrypted
+        .save(std::path::Path::new(&path))
+        .map_err(|_| SafeMaskError::ProofGenerationFailed)
+}
+
+/// Load a sealed blob from disk, decrypt it with `password`, and register a
+/// fresh unlocked wallet for the session.
+pub fn load_wallet(path: String, password: String) -> Result<WalletHandle, SafeMaskError> {
+    let encrypted = SafeMask_core::EncryptedState::load(std::path::Path::new(&path))
+        .map_err(|_| SafeMaskError::InvalidPassword)?;
+    let secrets = encrypted
+        .open(password.as_bytes())
+        .map_err(|_| SafeMaskError::InvalidPassword)?;
+
+    let wallet = SafeMaskWallet::from_secrets(&secrets, &password)
+        .map_err(|_| SafeMaskError::InvalidPassword)?;
+
+    let mut next_id = NEXT_ID.lock().unwrap();
+    let id = *next_id;
+    *next_id += 1;
+
+    let mut wallets = WALLETS.lock().unwrap();
+    wallets.insert(id, Arc::new(Mutex::new(wallet)));
+
+    let mut states = LOCK_STATES.lock().unwrap();
+    states.insert(
+        id,
+        SafeMask_core::wallet_state::WalletState::Unlocked { encrypted, secrets },
+    );
+
+    Ok(WalletHandle { id })
+}
+
+/// Re-derive the session key and decrypt secrets into memory so spends and
+/// exports can proceed again.
+pub fn unlock_wallet(handle: WalletHandle, password: String) -> Result<(), SafeMaskError> {
+    let mut states = LOCK_STATES.lock().unwrap();
+    let state = states.get_mut(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    state
+        .unlock(password.as_bytes())
+        .map_err(|_| SafeMaskError::InvalidPassword)?;
+
+    let wallets = WALLETS.lock().unwrap();
+    if let Some(wallet) = wallets.get(&handle.id) {
+        let mut wallet = wallet.lock().unwrap();
+        let _ = wallet.unlock(&password);
+    }
+    Ok(())
+}
+
+/// Zeroize in-memory secrets, leaving only public data until the next
+/// [`unlock_wallet`].
+pub fn lock_wallet(handle: WalletHandle) -> Result<(), SafeMaskError> {
+    let mut states = LOCK_STATES.lock().unwrap();
+    let state = states.get_mut(&handle.id).ok_or(SafeMaskError::WalletNotFound)?;
+    state.lock();
+
+    let wallets = WALLETS.lock().unwrap();
+    if let Some(wallet) = wallets.get(&handle.id) {
+        let mut wallet = wallet.lock().unwrap();
+        wallet.lock();
+    }
+    Ok(())
+}
+
 pub fn export_view_key(handle: WalletHandle) -> Result<String, SafeMaskError> {
     let wallets = WALLETS.lock().unwrap();
     let wallet = wallets.get(&handle.id)